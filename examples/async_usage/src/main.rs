@@ -20,7 +20,7 @@ fn main() {
         print!(", old status \"{}\"", old_status);
         print!(", new status \"{}\"", status);
         match error {
-            None => println!(""),
+            None => println!(),
             Some(err) => println!(", Error: \"{}\"", err),
         }
     };
@@ -30,7 +30,8 @@ fn main() {
     exec.start(vec![
         AsyncTarget::from((icmp_target, handler, Duration::from_secs(1))),
         AsyncTarget::from((tcp_target, handler, Duration::from_secs(1))),
-    ]);
+    ])
+    .unwrap();
     sleep(Duration::from_secs(3));
     exec.stop();
 }
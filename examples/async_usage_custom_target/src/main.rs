@@ -36,7 +36,7 @@ fn main() {
 
     // Spawn async executor
     let mut exec = AsyncTargetExecutor::new();
-    exec.start(targets);
+    exec.start(targets).unwrap();
     sleep(Duration::from_secs(3));
     exec.stop();
 }
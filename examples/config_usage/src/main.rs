@@ -0,0 +1,28 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+use std::thread::sleep;
+use std::time::Duration;
+
+use reachable::*;
+
+fn main() {
+    // Load AsyncTargets from configuration file
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/monitoring.toml");
+    let targets = match config::load(path) {
+        Ok(targets) => targets,
+        Err(error) => {
+            println!("Failed to load configuration: {}", error);
+            return;
+        }
+    };
+
+    // Spawn async executor
+    let mut exec = AsyncTargetExecutor::new();
+    exec.start(targets).unwrap();
+    sleep(Duration::from_secs(3));
+    exec.stop();
+}
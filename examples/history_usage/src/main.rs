@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+use reachable::history::HistoryStore;
+use reachable::*;
+
+fn main() {
+    // Setup AsyncTargets
+    let tcp_target = TcpTarget::from_str("www.google.de:80").unwrap();
+    let handler = |_: &dyn Target, _, _, _| {};
+
+    // Setup history, recording all checks in a file
+    let path = std::env::temp_dir().join("reachable-history.log");
+    let history = match HistoryStore::open(&path) {
+        Ok(history) => history,
+        Err(error) => {
+            println!("Failed to open history: {}", error);
+            return;
+        }
+    };
+
+    // Spawn Async executor feeding the history
+    let started = SystemTime::now();
+    let mut exec = AsyncTargetExecutor::new();
+    exec.add_observer(history);
+    exec.start(vec![AsyncTarget::from((tcp_target, handler, Duration::from_secs(1)))])
+        .unwrap();
+    sleep(Duration::from_secs(3));
+    exec.stop();
+
+    // Report uptime since start
+    let history = HistoryStore::open(&path).unwrap();
+    match history.uptime("www.google.de:80", started, SystemTime::now()) {
+        Ok(Some(uptime)) => println!("www.google.de:80 was {:.2}% available", uptime),
+        Ok(None) => println!("www.google.de:80 was not checked"),
+        Err(error) => println!("Failed to read history: {}", error),
+    }
+}
@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+use reachable::prometheus::PrometheusExporter;
+use reachable::*;
+
+fn main() {
+    // Setup AsyncTargets
+    let tcp_target = TcpTarget::from_str("www.google.de:80").unwrap();
+    let handler = |_: &dyn Target, _, _, _| {};
+
+    // Setup exporter serving metrics on http://127.0.0.1:9185/metrics
+    let exporter = PrometheusExporter::new();
+    let server = match exporter.serve("127.0.0.1:9185") {
+        Ok(server) => server,
+        Err(error) => {
+            println!("Failed to serve metrics: {}", error);
+            return;
+        }
+    };
+
+    // Spawn Async executor feeding the exporter
+    let mut exec = AsyncTargetExecutor::new();
+    exec.add_observer(exporter.clone());
+    exec.start(vec![AsyncTarget::from((tcp_target, handler, Duration::from_secs(1)))])
+        .unwrap();
+    sleep(Duration::from_secs(3));
+    exec.stop();
+
+    println!(
+        "Metrics served on http://{}/metrics:\n{}",
+        server.local_addr(),
+        exporter.render()
+    );
+}
@@ -0,0 +1,45 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+use reachable::status_api::StatusApi;
+use reachable::*;
+
+fn main() {
+    // Setup AsyncTargets
+    let icmp_target = IcmpTarget::from_str("www.google.de").unwrap();
+    let tcp_target = TcpTarget::from_str("www.google.de:80").unwrap();
+    let handler = |_: &dyn Target, _, _, _| {};
+
+    // Setup status API serving the status page on http://127.0.0.1:8080/
+    let status_api = StatusApi::new();
+    let server = match status_api.serve("127.0.0.1:8080") {
+        Ok(server) => server,
+        Err(error) => {
+            println!("Failed to serve status API: {}", error);
+            return;
+        }
+    };
+
+    // Spawn Async executor feeding the status API
+    let mut exec = AsyncTargetExecutor::new();
+    exec.add_observer(status_api.clone());
+    exec.start(vec![
+        AsyncTarget::from((icmp_target, handler, Duration::from_secs(1))),
+        AsyncTarget::from((tcp_target, handler, Duration::from_secs(1))),
+    ])
+    .unwrap();
+    sleep(Duration::from_secs(3));
+    exec.stop();
+
+    println!("Status page served on http://{}/", server.local_addr());
+    for target in status_api.targets() {
+        println!("{:?}", target);
+    }
+}
@@ -0,0 +1,33 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+use reachable::webhook::WebhookNotifier;
+use reachable::*;
+
+fn main() {
+    // Setup AsyncTargets
+    let tcp_target = TcpTarget::from_str("www.google.de:80").unwrap();
+    let handler = |_: &dyn Target, _, _, _| {};
+
+    // Setup notifier posting all transitions as chat message to a local webhook
+    let notifier = WebhookNotifier::from_str("http://127.0.0.1:8080/hook")
+        .unwrap()
+        .set_payload_template(String::from("{\"text\":\"{{summary}}\"}"))
+        .set_notify_initial(true)
+        .set_error_handler(Box::new(|error| println!("Failed to notify webhook: {}", error)));
+
+    // Spawn Async executor feeding the notifier
+    let mut exec = AsyncTargetExecutor::new();
+    exec.add_observer(notifier);
+    exec.start(vec![AsyncTarget::from((tcp_target, handler, Duration::from_secs(1)))])
+        .unwrap();
+    sleep(Duration::from_secs(3));
+    exec.stop();
+}
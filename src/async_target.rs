@@ -9,14 +9,25 @@
 //! # Notes
 //! Requires crate to be configured with feature "async".
 
-use super::{CheckTargetError, Status, Target};
-use futures::future::{join, join_all, BoxFuture, FutureExt};
-use std::thread::{spawn, JoinHandle};
-use std::time::Duration;
-use tokio::runtime::{self};
+use super::schedule::{CheckSchedule, MaintenanceWindow};
+use super::{CheckTargetError, DependencyError, Status, Target};
+use futures::executor::block_on;
+use futures::future::join_all;
+use std::any::Any;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, TryLockError};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::runtime::{self, Handle, Runtime};
 use tokio::select;
-use tokio::sync::watch::{self, Receiver, Sender};
-use tokio::task::{self};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot::{self, Receiver, Sender};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::{self, JoinHandle};
 use tokio::time::{self};
 
 /// Alias on [Status] to distinct between status of previous availability
@@ -29,15 +40,260 @@ pub type BoxedTarget<'a> = Box<dyn Target + Send + 'a>;
 /// Type containing a boxed trait object implementing [FnMut] that is called with each async check.
 pub type BoxedHandler<'a> = Box<dyn FnMut(&dyn Target, Status, OldStatus, Option<CheckTargetError>) + Send + 'a>;
 
+/// Type for a shared trait object implementing [CheckObserver]
+pub type SharedObserver = Arc<dyn CheckObserver>;
+
+/// Type for an [AsyncTarget] shared between its periodic check task and its checks.
+type SharedTarget = Arc<Mutex<AsyncTarget<'static>>>;
+
+/// Result of a single availability check, reported to each [CheckObserver] of an
+/// [AsyncTargetExecutor].
+///
+/// # Notes
+/// Further fields may be added in future versions. Outside of this crate, e.g. to test a custom
+/// [CheckObserver], construct reports with [CheckReport::new].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CheckReport<'a> {
+    /// Identifier of the checked [Target].
+    pub id: &'a str,
+    /// Confirmed status after the check. See [AsyncTarget::set_hysteresis].
+    pub status: &'a Status,
+    /// Confirmed status before the check.
+    pub old_status: &'a OldStatus,
+    /// Error occurred during the check, if any.
+    pub error: Option<&'a CheckTargetError>,
+    /// Time spent checking the target, including all retries.
+    pub duration: Duration,
+    /// Point in time the check finished.
+    pub timestamp: SystemTime,
+    /// True, if the check finished during a maintenance window of the target. See
+    /// [AsyncTarget::add_maintenance_window].
+    pub maintenance: bool,
+    /// Identifier of a parent, if the check failed while the parent was neither available nor
+    /// degraded. See [AsyncTarget::add_parent].
+    pub unreachable_parent: Option<&'a str>,
+    /// True, if the target is flapping after the check. See [AsyncTarget::set_flapping_policy].
+    pub flapping: bool,
+}
+
+impl<'a> CheckReport<'a> {
+    /// Construct a report of a check outside of maintenance windows, neither affected by parents
+    /// nor flapping.
+    pub fn new(
+        id: &'a str,
+        status: &'a Status,
+        old_status: &'a OldStatus,
+        error: Option<&'a CheckTargetError>,
+        duration: Duration,
+        timestamp: SystemTime,
+    ) -> Self {
+        CheckReport {
+            id,
+            status,
+            old_status,
+            error,
+            duration,
+            timestamp,
+            maintenance: false,
+            unreachable_parent: None,
+            flapping: false,
+        }
+    }
+}
+
+/// Trait for types observing the checks of all targets of an [AsyncTargetExecutor],
+/// e.g. metric exporters. In contrast to a [BoxedHandler], an observer is shared between all targets.
+pub trait CheckObserver: Send + Sync {
+    /// Called after each availability check, before the check handler of the target is called.
+    fn on_check(&self, report: &CheckReport);
+
+    /// Called if the check handler of the target with given id panicked with given message. The
+    /// panic was caught, the target keeps being checked.
+    fn on_handler_panic(&self, _id: &str, _message: &str) {}
+
+    /// Called after [CheckObserver::on_check], if the target started or stopped flapping with
+    /// the reported check. See [CheckReport::flapping].
+    fn on_flapping(&self, _report: &CheckReport) {}
+
+    /// Called after a target with given id was removed by [AsyncTargetExecutor::reload].
+    fn on_remove(&self, _id: &str) {}
+}
+
+/// Policy deciding the time between two checks of an [AsyncTarget], based on the latest result.
+///
+/// While the target is reachable, the healthy interval is used. Once a check fails, the target is
+/// re-checked after the failing interval, e.g. to confirm an outage quickly. While the target stays
+/// [Status::NotAvailable], the interval grows by the backoff factor with each check, until it
+/// reaches the maximum interval.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use reachable::IntervalPolicy;
+/// let policy = IntervalPolicy::new(Duration::from_secs(60))
+///     .set_failing_interval(Duration::from_secs(5))
+///     .set_backoff(2.0, Duration::from_secs(600));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalPolicy {
+    healthy_interval: Duration,
+    failing_interval: Duration,
+    backoff_factor: f64,
+    max_interval: Duration,
+}
+
+impl IntervalPolicy {
+    /// Construct an [IntervalPolicy] using given interval regardless of the check results.
+    pub fn new(interval: Duration) -> Self {
+        IntervalPolicy {
+            healthy_interval: interval,
+            failing_interval: interval,
+            backoff_factor: 1.0,
+            max_interval: interval,
+        }
+    }
+
+    /// Set the interval used after a check did neither report [Status::Available] nor
+    /// [Status::Degraded]. Defaults to the healthy interval.
+    pub fn set_failing_interval(mut self, failing_interval: Duration) -> Self {
+        self.failing_interval = failing_interval;
+        self
+    }
+
+    /// Set the exponential backoff, applied to the failing interval while the target stays
+    /// [Status::NotAvailable]. The interval is multiplied by factor with each further check, up to
+    /// the maximum interval. Defaults to no backoff, factors below 1.0 are treated as 1.0.
+    pub fn set_backoff(mut self, factor: f64, max_interval: Duration) -> Self {
+        self.backoff_factor = factor.max(1.0);
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Get the interval used while the target is reachable.
+    pub fn get_healthy_interval(&self) -> &Duration {
+        &self.healthy_interval
+    }
+
+    /// Get the interval used after a failed check.
+    pub fn get_failing_interval(&self) -> &Duration {
+        &self.failing_interval
+    }
+
+    /// Get the backoff factor.
+    pub fn get_backoff_factor(&self) -> f64 {
+        self.backoff_factor
+    }
+
+    /// Get the upper bound of the interval during backoff.
+    pub fn get_max_interval(&self) -> &Duration {
+        &self.max_interval
+    }
+
+    /// Get the interval until the next check.
+    ///
+    /// # Arguments
+    /// * status: result of the latest check.
+    /// * not_available: number of consecutive checks reporting [Status::NotAvailable].
+    pub fn interval(&self, status: &Status, not_available: u32) -> Duration {
+        match status {
+            Status::Available | Status::Degraded(_) => self.healthy_interval,
+            Status::NotAvailable if not_available > 1 && self.backoff_factor > 1.0 => {
+                let exponent = (not_available - 1).min(i32::MAX as u32) as i32;
+                let interval = self.failing_interval.as_secs_f64() * self.backoff_factor.powi(exponent);
+                let max_interval = self.max_interval.max(self.failing_interval);
+                match interval < max_interval.as_secs_f64() {
+                    true => Duration::from_secs_f64(interval),
+                    false => max_interval,
+                }
+            }
+            _ => self.failing_interval,
+        }
+    }
+}
+
+impl From<Duration> for IntervalPolicy {
+    fn from(interval: Duration) -> Self {
+        IntervalPolicy::new(interval)
+    }
+}
+
+/// Policy detecting a flapping [AsyncTarget], whose status changes too often.
+///
+/// All status changes within a sliding time window are counted. Once the count reaches the start
+/// threshold, the target is flapping. It stops flapping, once the count dropped to the stop
+/// threshold.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use reachable::FlappingPolicy;
+/// // Flapping after 5 status changes within 10 minutes, until at most 1 change is left
+/// let policy = FlappingPolicy::new(Duration::from_secs(600), 5, 1);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlappingPolicy {
+    window: Duration,
+    start_threshold: usize,
+    stop_threshold: usize,
+}
+
+impl FlappingPolicy {
+    /// Construct a [FlappingPolicy].
+    ///
+    /// # Arguments
+    /// * window: time [Duration] status changes are counted in.
+    /// * start_threshold: number of status changes within the window, starting flapping. A value
+    ///   of 0 is treated as 1.
+    /// * stop_threshold: number of status changes within the window, stopping flapping. Values
+    ///   not below the start threshold are treated as the start threshold minus 1.
+    pub fn new(window: Duration, start_threshold: usize, stop_threshold: usize) -> Self {
+        let start_threshold = start_threshold.max(1);
+        FlappingPolicy {
+            window,
+            start_threshold,
+            stop_threshold: stop_threshold.min(start_threshold - 1),
+        }
+    }
+
+    /// Get the time window, status changes are counted in.
+    pub fn get_window(&self) -> &Duration {
+        &self.window
+    }
+
+    /// Get the number of status changes starting flapping.
+    pub fn get_start_threshold(&self) -> usize {
+        self.start_threshold
+    }
+
+    /// Get the number of status changes stopping flapping.
+    pub fn get_stop_threshold(&self) -> usize {
+        self.stop_threshold
+    }
+}
+
 /// Struct storing all data used during asynchronous execution.
 ///
 /// For async check execution, wrap the instances of [Target] in [AsyncTarget] and hand them to
 /// [AsyncTargetExecutor::start].
 pub struct AsyncTarget<'a> {
-    target: BoxedTarget<'a>,
+    target: Arc<Mutex<BoxedTarget<'a>>>,
     check_handler: BoxedHandler<'a>,
-    check_interval: Duration,
+    interval_policy: IntervalPolicy,
+    schedule: Option<CheckSchedule>,
+    check_timeout: Option<Duration>,
+    retries: u32,
+    hysteresis: u32,
+    fingerprint: Option<String>,
+    maintenance_windows: Vec<MaintenanceWindow>,
+    parents: Vec<String>,
+    flapping_policy: Option<FlappingPolicy>,
+    transitions: VecDeque<(Instant, OldStatus)>,
+    flapping: Option<OldStatus>,
     status: Status,
+    pending: Option<(Status, u32)>,
+    last_result: Status,
+    not_available: u32,
 }
 
 impl<'a> AsyncTarget<'a> {
@@ -52,10 +308,258 @@ impl<'a> AsyncTarget<'a> {
     /// Instance of [AsyncTarget].
     pub fn new(target: BoxedTarget<'a>, check_handler: BoxedHandler<'a>, check_interval: Duration) -> Self {
         AsyncTarget {
-            target,
+            target: Arc::new(Mutex::new(target)),
             check_handler,
-            check_interval,
+            interval_policy: IntervalPolicy::new(check_interval),
+            schedule: None,
+            check_timeout: None,
+            retries: 0,
+            hysteresis: 1,
+            fingerprint: None,
+            maintenance_windows: Vec::new(),
+            parents: Vec::new(),
+            flapping_policy: None,
+            transitions: VecDeque::new(),
+            flapping: None,
             status: Status::Unknown,
+            pending: None,
+            last_result: Status::Unknown,
+            not_available: 0,
+        }
+    }
+
+    /// Set the number of additional check attempts, performed if [Target::check_availability]
+    /// did neither report [Status::Available] nor [Status::Degraded]. Defaults to 0.
+    pub fn set_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the [IntervalPolicy], replacing the check interval given on construction.
+    pub fn set_interval_policy(mut self, interval_policy: IntervalPolicy) -> Self {
+        self.interval_policy = interval_policy;
+        self
+    }
+
+    /// Set a wall-clock based [CheckSchedule], replacing the [IntervalPolicy] in scheduling checks.
+    ///
+    /// Checks are performed at the points in time given by the schedule, regardless of their
+    /// results and duration. If a check takes longer than the schedule, missed points in time are
+    /// skipped.
+    ///
+    /// # Notes
+    /// The first check is performed at the first point in time of the schedule, not on start.
+    /// Jitter and spread start of the [AsyncTargetExecutor] do not apply to scheduled targets. If
+    /// the schedule is never due, the [IntervalPolicy] is used instead.
+    pub fn set_schedule(mut self, schedule: CheckSchedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Set the deadline of a single check, including all retries. Defaults to no deadline.
+    ///
+    /// If the check did not finish in time, it is reported as [Status::Unknown] with a timeout
+    /// error and the next check is scheduled as usual. The stuck check is not waited for.
+    ///
+    /// # Notes
+    /// A stuck [Target::check_availability] call can not be cancelled. Until it returns, the
+    /// following checks fail immediately and the check handler receives a stand-in [Target],
+    /// providing the id of the wrapped [Target] only.
+    pub fn set_check_timeout(mut self, check_timeout: Duration) -> Self {
+        self.check_timeout = Some(check_timeout);
+        self
+    }
+
+    /// Set the number of consecutive identical check results required, before the status of
+    /// this [AsyncTarget] changes. Defaults to 1, a value of 0 is treated as 1.
+    ///
+    /// # Notes
+    /// Until a new status was confirmed, the check handler receives the previously confirmed
+    /// status. Errors are always forwarded to the check handler. Changes of the reason of
+    /// [Status::Degraded] are no status changes and take effect immediately.
+    pub fn set_hysteresis(mut self, hysteresis: u32) -> Self {
+        self.hysteresis = hysteresis.max(1);
+        self
+    }
+
+    /// Add a [MaintenanceWindow], during which the target is expected to be unavailable.
+    ///
+    /// Checks are performed and the status is updated as usual during an active window, but the
+    /// check handler is not called. Observers are notified with [CheckReport::maintenance] set,
+    /// allowing them to treat the result as expected downtime.
+    pub fn add_maintenance_window(mut self, window: MaintenanceWindow) -> Self {
+        self.maintenance_windows.push(window);
+        self
+    }
+
+    /// Add a parent, this target depends on, e.g. the gateway in front of it. The parent is given
+    /// by the id of its [Target] and must be checked by the same [AsyncTargetExecutor].
+    ///
+    /// If a check fails while a checked parent is neither [Status::Available] nor
+    /// [Status::Degraded], the target is unreachable due to its parent: The status is updated as
+    /// usual, the check handler receives [CheckTargetError::UnreachableParent] as error and
+    /// observers are notified with [CheckReport::unreachable_parent] set.
+    pub fn add_parent(mut self, id: &str) -> Self {
+        self.parents.push(String::from(id));
+        self
+    }
+
+    /// Set the [FlappingPolicy]. Defaults to no flapping detection.
+    ///
+    /// While flapping, the status is updated as usual, but the check handler is not called.
+    /// Observers are notified about the start and stop of flapping via
+    /// [CheckObserver::on_flapping]. Once flapping stopped, the check handler receives the status
+    /// before flapping started as old status.
+    pub fn set_flapping_policy(mut self, flapping_policy: FlappingPolicy) -> Self {
+        self.flapping_policy = Some(flapping_policy);
+        self
+    }
+
+    /// Set a fingerprint describing the configuration of this [AsyncTarget].
+    ///
+    /// On [AsyncTargetExecutor::reload], a running target with equal id and fingerprint is
+    /// considered unchanged and keeps running. Targets without fingerprint are always restarted.
+    pub fn set_fingerprint(mut self, fingerprint: String) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
+    /// Get the fingerprint, if one was set.
+    pub fn get_fingerprint(&self) -> Option<&String> {
+        self.fingerprint.as_ref()
+    }
+
+    /// Get the identifier of the wrapped [Target].
+    pub fn get_id(&self) -> String {
+        lock(&self.target).get_id()
+    }
+
+    /// Get the ids of all parents.
+    pub fn get_parents(&self) -> &Vec<String> {
+        &self.parents
+    }
+
+    /// Get the [FlappingPolicy], if one was set.
+    pub fn get_flapping_policy(&self) -> Option<&FlappingPolicy> {
+        self.flapping_policy.as_ref()
+    }
+
+    /// Check if the target is currently flapping.
+    pub fn is_flapping(&self) -> bool {
+        self.flapping.is_some()
+    }
+
+    /// Get all [MaintenanceWindow]s.
+    pub fn get_maintenance_windows(&self) -> &Vec<MaintenanceWindow> {
+        &self.maintenance_windows
+    }
+
+    /// Check if a maintenance window is active at given point in time.
+    pub fn in_maintenance(&self, time: SystemTime) -> bool {
+        self.maintenance_windows.iter().any(|window| window.is_active(time))
+    }
+
+    /// Get the check interval [Duration] used while the target is reachable.
+    pub fn get_check_interval(&self) -> &Duration {
+        self.interval_policy.get_healthy_interval()
+    }
+
+    /// Get the [IntervalPolicy].
+    pub fn get_interval_policy(&self) -> &IntervalPolicy {
+        &self.interval_policy
+    }
+
+    /// Get the [CheckSchedule], if one was set.
+    pub fn get_schedule(&self) -> Option<&CheckSchedule> {
+        self.schedule.as_ref()
+    }
+
+    /// Get the next point in time after given time, a check is due according to the schedule.
+    fn next_scheduled(&self, time: SystemTime) -> Option<SystemTime> {
+        self.schedule.as_ref().and_then(|schedule| schedule.next_after(time))
+    }
+
+    /// Get the time until the next check, based on the latest check result.
+    fn next_interval(&self) -> Duration {
+        self.interval_policy.interval(&self.last_result, self.not_available)
+    }
+
+    /// Get the check deadline [Duration], if one was set.
+    pub fn get_check_timeout(&self) -> Option<&Duration> {
+        self.check_timeout.as_ref()
+    }
+
+    /// Get the number of additional check attempts.
+    pub fn get_retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Get the number of consecutive identical check results required for a status change.
+    pub fn get_hysteresis(&self) -> u32 {
+        self.hysteresis
+    }
+
+    /// Get the current [Status].
+    pub fn get_status(&self) -> &Status {
+        &self.status
+    }
+
+    /// Feed a check result into the stored status, applying the configured hysteresis.
+    ///
+    /// # Returns
+    /// The tuple of the confirmed status and the previously confirmed status.
+    fn update_status(&mut self, status: Status) -> (Status, OldStatus) {
+        let old_status = self.status.clone();
+
+        // Track the raw result, the interval policy reacts without hysteresis
+        self.not_available = match status {
+            Status::NotAvailable => self.not_available.saturating_add(1),
+            _ => 0,
+        };
+        self.last_result = status.clone();
+
+        // Note: A change of the degradation reason is no transition. The reason is updated
+        // immediately, while changes of the state itself are subject to hysteresis.
+        if status.same_state(&self.status) {
+            self.status = status;
+            self.pending = None;
+        } else {
+            let count = match self.pending.take() {
+                Some((pending, count)) if pending.same_state(&status) => count + 1,
+                _ => 1,
+            };
+
+            if count >= self.hysteresis {
+                self.status = status;
+            } else {
+                self.pending = Some((status, count));
+            }
+        }
+        (self.status.clone(), old_status)
+    }
+
+    /// Track a status change at given point in time and update the flapping state.
+    ///
+    /// # Returns
+    /// The status before flapping started, if flapping stopped.
+    fn update_flapping(&mut self, status: &Status, old_status: &OldStatus, now: Instant) -> Option<OldStatus> {
+        let policy = self.flapping_policy.as_ref()?;
+        if !status.same_state(old_status) {
+            self.transitions.push_back((now, old_status.clone()));
+        }
+        while matches!(self.transitions.front(), Some((time, _)) if now.duration_since(*time) > policy.window) {
+            self.transitions.pop_front();
+        }
+
+        let count = self.transitions.len();
+        match self.flapping {
+            None if count >= policy.start_threshold => {
+                // Note: The oldest change within the window marks the begin of flapping
+                self.flapping = self.transitions.front().map(|(_, old_status)| old_status.clone());
+                None
+            }
+            Some(_) if count <= policy.stop_threshold => self.flapping.take(),
+            _ => None,
         }
     }
 }
@@ -78,25 +582,198 @@ where
 
 /// Async target check executor used to check the availability of a given number of [AsyncTarget]s.
 pub struct AsyncTargetExecutor {
-    /// Optional threadhandle and synchronization channel to executing runtime.
-    worker: Option<(JoinHandle<()>, Sender<()>)>,
+    /// Optional runtime executing all periodic availability checks, owned by this executor.
+    runtime: Option<Runtime>,
+    /// Handle of the runtime executing all periodic availability checks. Set while started.
+    handle: Option<Handle>,
+    /// All [AsyncTarget]s currently checked.
+    running: Vec<RunningTarget>,
+    /// All [CheckObserver]s notified about each check.
+    observers: Arc<RwLock<Vec<SharedObserver>>>,
+    /// Confirmed status of all targets, used to resolve their dependencies.
+    statuses: Arc<Statuses>,
+    /// Limits applied to the checks of all targets.
+    scheduling: Scheduling,
+}
+
+/// Confirmed status of all targets by id.
+type Statuses = RwLock<HashMap<String, Status>>;
+
+/// Maximum number of threads of the blocking pool of a runtime owned by an [AsyncTargetExecutor].
+/// Each running check occupies one of these threads.
+const MAX_BLOCKING_THREADS: usize = 512;
+
+/// Upper bounds of the buckets of a [DurationHistogram] in milliseconds.
+const HISTOGRAM_BUCKETS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Executor-wide scheduling limits and self-metrics, shared between all periodic check tasks.
+#[derive(Clone, Default)]
+struct Scheduling {
+    /// Self-metrics recorded by all checks. See [AsyncTargetExecutor::get_metrics].
+    metrics: Arc<MetricsRecorder>,
+    /// Permits of concurrently running checks. See [AsyncTargetExecutor::set_max_concurrent_checks].
+    concurrency: Option<Arc<Semaphore>>,
+    /// Minimum time between the start of two checks. See [AsyncTargetExecutor::set_rate_limit].
+    spacing: Option<Duration>,
+    /// Earliest point in time, the next check may start.
+    next_slot: Arc<Mutex<Option<Instant>>>,
+    /// Upper bound of the random delay added to each interval. See [AsyncTargetExecutor::set_jitter].
+    jitter: Option<Duration>,
+    /// Delay the first check of each target randomly. See [AsyncTargetExecutor::set_spread_start].
+    spread_start: bool,
+}
+
+impl Scheduling {
+    /// Wait until the rate limit allows another check and a concurrency permit is available.
+    ///
+    /// # Returns
+    /// The concurrency permit, to be held until the check finished.
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        if let Some(spacing) = self.spacing {
+            let slot = {
+                let now = Instant::now();
+                let mut next_slot = self.next_slot.lock().unwrap();
+                let slot = next_slot.map_or(now, |next_slot| next_slot.max(now));
+                *next_slot = Some(slot + spacing);
+                slot
+            };
+            time::sleep_until(time::Instant::from_std(slot)).await;
+        }
+
+        match &self.concurrency {
+            Some(concurrency) => concurrency.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+}
+
+/// Self-metrics of an [AsyncTargetExecutor], recorded by all periodic check tasks.
+#[derive(Default)]
+struct MetricsRecorder {
+    /// Number of checks currently performed.
+    in_flight_checks: Arc<AtomicUsize>,
+    /// Number of checks occupying or waiting for a thread of the blocking pool.
+    blocking_checks: Arc<AtomicUsize>,
+    /// Metrics of all targets by id.
+    targets: Mutex<HashMap<String, TargetMetrics>>,
+}
+
+impl MetricsRecorder {
+    /// Record a measurement in the metrics of the target with given id.
+    fn record(&self, id: &str, record: impl FnOnce(&mut TargetMetrics)) {
+        record(lock(&self.targets).entry(id.to_string()).or_default());
+    }
+}
+
+/// Guard counting an occupied resource as long as it is alive.
+struct Occupation(Arc<AtomicUsize>);
+
+impl Occupation {
+    /// Occupy a resource counted by given counter.
+    fn new(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Occupation(counter.clone())
+    }
+}
+
+impl Drop for Occupation {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Bookkeeping of an [AsyncTarget] currently checked by an [AsyncTargetExecutor].
+struct RunningTarget {
+    /// Identifier of the checked [Target].
+    id: String,
+    /// Fingerprint of the checked [AsyncTarget]. See [AsyncTarget::set_fingerprint].
+    fingerprint: Option<String>,
+    /// Synchronization channel to stop the periodic check task. Carries the deadline of an
+    /// in-flight check.
+    teardown_send: Sender<Instant>,
+    /// Channel to control the periodic check task.
+    control_send: UnboundedSender<Control>,
+    /// Handle of the periodic check task. The task returns the checked target on termination.
+    task: JoinHandle<SharedTarget>,
+}
+
+/// Control messages, sent to a periodic check task.
+enum Control {
+    /// Suspend the periodic checks.
+    Pause,
+    /// Continue the periodic checks.
+    Resume,
+    /// Check immediately and reply the confirmed status afterwards.
+    CheckNow(Sender<Status>),
+    /// Replace the interval policy.
+    SetInterval(IntervalPolicy),
 }
 
 impl AsyncTargetExecutor {
     /// Construct a new [AsyncTargetExecutor]
     pub fn new() -> Self {
         AsyncTargetExecutor {
-            worker: None,
+            runtime: None,
+            handle: None,
+            running: Vec::new(),
+            observers: Arc::default(),
+            statuses: Arc::default(),
+            scheduling: Scheduling::default(),
         }
     }
 
+    /// Set the maximum number of checks running at the same time. Checks exceeding the limit
+    /// wait until a running check finished. Defaults to no limit, a value of 0 is treated as 1.
+    ///
+    /// # Notes
+    /// Each check occupies a thread of the blocking thread pool of the runtime. Without limit,
+    /// many targets with the same interval saturate the pool at once.
+    pub fn set_max_concurrent_checks(mut self, max_concurrent_checks: usize) -> Self {
+        self.scheduling.concurrency = Some(Arc::new(Semaphore::new(max_concurrent_checks.max(1))));
+        self
+    }
+
+    /// Set the maximum number of checks started per second, across all targets. Checks exceeding
+    /// the limit are delayed. Defaults to no limit, a value of 0 is treated as 1.
+    pub fn set_rate_limit(mut self, checks_per_second: u32) -> Self {
+        self.scheduling.spacing = Some(Duration::from_secs(1) / checks_per_second.max(1));
+        self
+    }
+
+    /// Set the upper bound of a random delay, added to each check interval. Defaults to no jitter.
+    pub fn set_jitter(mut self, jitter: Duration) -> Self {
+        self.scheduling.jitter = Some(jitter);
+        self
+    }
+
+    /// Delay the first check of each target by a random offset within its check interval,
+    /// spreading the checks of targets with the same interval evenly. Defaults to false.
+    pub fn set_spread_start(mut self, spread_start: bool) -> Self {
+        self.scheduling.spread_start = spread_start;
+        self
+    }
+
+    /// Add an observer, notified about the checks of all targets of this executor.
+    ///
+    /// Observers can be added at any time and are notified about all following checks.
+    pub fn add_observer<T: CheckObserver + 'static>(&mut self, observer: T) {
+        self.observers.write().unwrap().push(Arc::new(observer));
+    }
+
     /// Start periodic availability checks for all given targets
     ///
     /// Each targets execution behavior is configured during [AsyncTarget] construction.
+    /// If the executor was already started, the given targets are ignored. Use
+    /// [AsyncTargetExecutor::reload] to change the targets of a running executor.
     ///
     /// # Arguments
     /// * targets: a vector of [AsyncTarget]s, those availability should be check periodically.
     ///
+    /// # Errors
+    /// Returns a [DependencyError], if multiple targets share an id, a parent of a target is not
+    /// part of the given targets or the parents form a cycle (see [AsyncTarget::add_parent]). No
+    /// target is started then.
+    ///
     /// # Example
     /// ```
     /// # use std::{str::FromStr, thread::sleep, time::Duration};
@@ -112,101 +789,925 @@ impl AsyncTargetExecutor {
     ///
     /// // Setup AsyncTargetExecutor and let it run for 1s
     /// let mut exec = AsyncTargetExecutor::new();
-    /// exec.start(vec![async_target]);
+    /// exec.start(vec![async_target]).unwrap();
     /// sleep(Duration::from_secs(1));
     /// exec.stop();
     /// ```
-    pub fn start(&mut self, targets: Vec<AsyncTarget<'static>>) {
-        if self.worker.is_none() {
-            // Setup teardown mechanism and construct runtime
-            let (teardown_send, teardown_recv) = watch::channel(());
-            let runtime = runtime::Builder::new_multi_thread().enable_time().build().unwrap();
-
-            // Convert all targets into BoxFutures and execute them afterwards
-            let tasks: Vec<BoxFuture<()>> = targets
-                .into_iter()
-                .map(|target| check_target_periodically(target, teardown_recv.clone()).boxed())
-                .collect();
-
-            // Spawn eventloop in a dedicated thread.
-            // Note: After sending a shutdown message, all spawend tasks terminate.
-            // The Problem here is that some async calles were offloaded to dedicated processing
-            // threads. For a runtime to shutdown, these threads must have been processed, this
-            // causes potentially a huge delay.
-            // To prevent this, all unfinished tasks are moved to a detached thread
-            // allowing this thread to terminate in a timely manner.
-            let handle = spawn(move || {
-                runtime.block_on(join_all(tasks));
-                runtime.shutdown_background();
-            });
+    pub fn start(&mut self, targets: Vec<AsyncTarget<'static>>) -> Result<(), DependencyError> {
+        if self.handle.is_none() {
+            let runtime = runtime::Builder::new_multi_thread()
+                .enable_time()
+                .max_blocking_threads(MAX_BLOCKING_THREADS)
+                .build()
+                .unwrap();
+            self.start_on(runtime.handle().clone(), targets)?;
+            self.runtime = Some(runtime);
+        }
+        Ok(())
+    }
 
-            self.worker = Some((handle, teardown_send));
+    /// Start periodic availability checks for all given targets on an existing tokio runtime,
+    /// instead of a runtime owned by this executor.
+    ///
+    /// If the executor was already started, the given targets are ignored.
+    ///
+    /// # Arguments
+    /// * handle: [Handle] of the runtime to spawn all check tasks onto. The runtime must have the
+    ///   time driver enabled.
+    /// * targets: a vector of [AsyncTarget]s, those availability should be check periodically.
+    ///
+    /// # Errors
+    /// Returns a [DependencyError], if the ids or dependencies of the given targets are invalid.
+    /// See [AsyncTargetExecutor::start].
+    ///
+    /// # Notes
+    /// Use [AsyncTargetExecutor::stop_async] to stop the executor from within the runtime.
+    /// [AsyncTargetExecutor::stop] and dropping the executor do not wait for the check tasks to
+    /// terminate, if called from within a runtime.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{str::FromStr, time::Duration};
+    /// # use reachable::*;
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let target = TcpTarget::from_str("127.0.0.1:80").unwrap();
+    ///     let check_handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {
+    ///         // Handle check results
+    ///     };
+    ///     let async_target = AsyncTarget::from((target, check_handler, Duration::from_secs(1)));
+    ///
+    ///     // Run the checks on the runtime of main for 1s
+    ///     let mut exec = AsyncTargetExecutor::new();
+    ///     exec.start_on(tokio::runtime::Handle::current(), vec![async_target]).unwrap();
+    ///     tokio::time::sleep(Duration::from_secs(1)).await;
+    ///     exec.stop_async().await;
+    /// }
+    /// ```
+    pub fn start_on(&mut self, handle: Handle, targets: Vec<AsyncTarget<'static>>) -> Result<(), DependencyError> {
+        if self.handle.is_none() {
+            verify_dependencies(&targets)?;
+            self.handle = Some(handle);
+            lock(&self.scheduling.metrics.targets).clear();
+            for target in targets.into_iter() {
+                self.spawn(target);
+            }
         }
+        Ok(())
     }
 
-    /// Stop asynchronous processing started with [AsyncTargetExecutor::start] gracefully.
-    pub fn stop(&mut self) {
-        if let Some((handle, teardown_send)) = self.worker.take() {
-            // Signal all async tasks to terminate and wait until runtime thread stopped.
-            teardown_send.send(()).unwrap();
-            handle.join().unwrap();
+    /// Replace the targets of a running executor without disturbing unchanged targets.
+    ///
+    /// The given targets are compared with the running targets by their id and fingerprint
+    /// (see [AsyncTarget::set_fingerprint]):
+    /// * Unchanged targets (same id and fingerprint) keep running with their current status.
+    ///   The given duplicate is dropped.
+    /// * Changed targets (same id, different or no fingerprint) are stopped and restarted.
+    /// * New targets are started.
+    /// * Running targets missing in the given targets are stopped.
+    ///
+    /// If the executor was not started yet, this is equivalent to [AsyncTargetExecutor::start].
+    ///
+    /// # Arguments
+    /// * targets: a vector of [AsyncTarget]s, replacing the currently checked targets.
+    ///
+    /// # Errors
+    /// Returns a [DependencyError], if the ids or dependencies of the given targets are invalid.
+    /// See [AsyncTargetExecutor::start]. The running targets are kept unchanged then.
+    ///
+    /// # Notes
+    /// Reload is usually triggered by a configuration change, e.g. on SIGHUP or after a file
+    /// watcher noticed a modification. Targets loaded via [config](crate::config) carry a
+    /// fingerprint of their configuration.
+    pub fn reload(&mut self, targets: Vec<AsyncTarget<'static>>) -> Result<(), DependencyError> {
+        if self.handle.is_none() {
+            return self.start(targets);
+        }
+        verify_dependencies(&targets)?;
+
+        let mut previous = std::mem::take(&mut self.running);
+        for target in targets.into_iter() {
+            let id = target.get_id();
+            match previous.iter().position(|running| running.id == id) {
+                Some(index) if target.fingerprint.is_some() && previous[index].fingerprint == target.fingerprint => {
+                    self.running.push(previous.swap_remove(index));
+                }
+                Some(index) => {
+                    let _ = previous.swap_remove(index).teardown_send.send(Instant::now());
+                    self.spawn(target);
+                }
+                None => self.spawn(target),
+            }
+        }
+
+        // Stop all remaining targets. They are not part of the new targets.
+        let observers = self.observers.read().unwrap();
+        for running in previous.into_iter() {
+            let _ = running.teardown_send.send(Instant::now());
+            self.statuses.write().unwrap().remove(&running.id);
+            lock(&self.scheduling.metrics.targets).remove(&running.id);
+            observers.iter().for_each(|observer| observer.on_remove(&running.id));
         }
+        Ok(())
     }
-}
 
-impl Default for AsyncTargetExecutor {
-    fn default() -> Self {
-        AsyncTargetExecutor::new()
+    /// Pause the periodic checks of the target with given id, e.g. during maintenance. The
+    /// target keeps its status until it is resumed.
+    ///
+    /// # Returns
+    /// True, if a target with given id is checked by this executor.
+    pub fn pause(&self, id: &str) -> bool {
+        self.control(id, Control::Pause)
     }
-}
 
-impl Drop for AsyncTargetExecutor {
-    fn drop(&mut self) {
-        self.stop()
+    /// Resume the periodic checks of a paused target with given id. If its check interval expired
+    /// while being paused, the target is checked immediately.
+    ///
+    /// # Returns
+    /// True, if a target with given id is checked by this executor.
+    pub fn resume(&self, id: &str) -> bool {
+        self.control(id, Control::Resume)
     }
-}
 
-async fn check_target_periodically(mut target: AsyncTarget<'static>, mut teardown_recv: Receiver<()>) {
-    loop {
-        target = select! {
-            // Teardown message was not received. Perform next check.
-            target = check_target(target) => target,
+    /// Check the target with given id immediately, instead of waiting for its check interval to
+    /// expire. Paused targets are checked once and stay paused. The check is handled like any
+    /// periodic check, the interval restarts afterwards.
+    ///
+    /// # Returns
+    /// A future resolving to the confirmed [Status] after the check. It resolves to None, if no
+    /// target with given id is checked by this executor or the target was stopped before.
+    ///
+    /// # Notes
+    /// The returned future does not borrow the executor. Outside of an async context, use
+    /// e.g. [futures::executor::block_on] to wait for the result.
+    pub fn check_now(&self, id: &str) -> impl Future<Output = Option<Status>> {
+        let (reply_send, reply_recv) = oneshot::channel();
+        let sent = self.control(id, Control::CheckNow(reply_send));
+        async move {
+            match sent {
+                true => reply_recv.await.ok(),
+                false => None,
+            }
+        }
+    }
 
-            // Teardown message was received: Stop processing
-            _ = teardown_recv.changed() => return,
-        };
+    /// Replace the check interval of the target with given id. Accepts a [Duration] or an
+    /// [IntervalPolicy]. The next check is rescheduled based on the new interval. A
+    /// [CheckSchedule] of the target is removed.
+    ///
+    /// # Returns
+    /// True, if a target with given id is checked by this executor.
+    pub fn set_interval<T: Into<IntervalPolicy>>(&self, id: &str, interval: T) -> bool {
+        self.control(id, Control::SetInterval(interval.into()))
     }
-}
 
-async fn check_target(mut target: AsyncTarget<'static>) -> AsyncTarget<'static> {
-    // Setup sleep timer to wait, to prevent further execution before the check_interval elapsed.
-    let sleep = time::sleep(target.check_interval);
+    /// Get a snapshot of the self-metrics of this executor, e.g. to verify that all checks are
+    /// performed in time.
+    ///
+    /// # Notes
+    /// The metrics of a target are kept until the executor is started again or the target is
+    /// removed by [AsyncTargetExecutor::reload].
+    pub fn get_metrics(&self) -> ExecutorMetrics {
+        let metrics = &self.scheduling.metrics;
+        ExecutorMetrics {
+            in_flight_checks: metrics.in_flight_checks.load(Ordering::SeqCst),
+            blocking_checks: metrics.blocking_checks.load(Ordering::SeqCst),
+            max_blocking_threads: self.runtime.as_ref().map(|_| MAX_BLOCKING_THREADS),
+            targets: lock(&metrics.targets).clone(),
+        }
+    }
 
-    // Offload potentially blocking check_availability call onto a separate thread
-    let task = task::spawn_blocking(|| {
-        // Check current target availability
-        let (status, error) = match target.target.check_availability() {
-            Ok(status) => (status, None),
-            Err(error) => (Status::Unknown, Some(error)),
-        };
+    /// Send a control message to the periodic check task of the target with given id.
+    fn control(&self, id: &str, control: Control) -> bool {
+        match self.running.iter().find(|running| running.id == id) {
+            Some(running) => running.control_send.send(control).is_ok(),
+            None => false,
+        }
+    }
 
-        // Update stored status
-        let old_status = target.status;
-        target.status = status.clone();
+    /// Stop asynchronous processing started with [AsyncTargetExecutor::start] gracefully.
+    /// In-flight checks are abandoned, see [AsyncTargetExecutor::stop_with_timeout].
+    ///
+    /// # Notes
+    /// If the executor was started on an existing runtime and this is called from within a
+    /// runtime, all check tasks are signaled to terminate, but not waited for. Blocking would
+    /// stall the runtime. Use [AsyncTargetExecutor::stop_async] instead.
+    pub fn stop(&mut self) {
+        self.stop_with_timeout(Duration::ZERO);
+    }
 
-        // Call stored Handler
-        target.check_handler.as_mut()(target.target.as_ref(), status, old_status, error);
-        target
-    });
+    /// Stop asynchronous processing gracefully, waiting for in-flight checks up to given timeout.
+    ///
+    /// Targets waiting for their next check are stopped immediately. Targets currently checked
+    /// are stopped, once their check was processed or the timeout expired. A check exceeding
+    /// the timeout is abandoned, its result is not reported.
+    ///
+    /// # Returns
+    /// The confirmed [Status] of all targets by id. Targets never checked are [Status::Unknown].
+    /// If the executor was not started, the map is empty.
+    ///
+    /// # Notes
+    /// If the executor was started on an existing runtime and this is called from within a
+    /// runtime, all check tasks are signaled to terminate, but not waited for. Use
+    /// [AsyncTargetExecutor::stop_with_timeout_async] instead.
+    pub fn stop_with_timeout(&mut self, timeout: Duration) -> HashMap<String, Status> {
+        if self.handle.take().is_none() {
+            return HashMap::new();
+        }
 
-    // Wait until the task was processed and the sleep interval expired. Return given async_target
-    let (tmp, _) = join(task, sleep).await;
-    tmp.unwrap()
-}
+        // Signal all async tasks to terminate and wait until all of them stopped.
+        let ids = self.running.iter().map(|running| running.id.clone()).collect();
+        let tasks = self.teardown(Instant::now() + timeout);
+        self.join(tasks);
+        self.final_statuses(ids)
+    }
 
-#[cfg(test)]
+    /// Stop asynchronous processing gracefully like [AsyncTargetExecutor::stop_with_timeout] and
+    /// hand back all checked targets. The returned targets keep their handler, configuration and
+    /// current [Status], they can be started again with any executor.
+    ///
+    /// # Returns
+    /// All targets checked by this executor. If the executor was not started, the list is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::time::Duration;
+    /// # use reachable::*;
+    /// # let targets = Vec::new();
+    /// let mut exec = AsyncTargetExecutor::new();
+    /// exec.start(targets).unwrap();
+    ///
+    /// // Restart all targets on a differently configured executor
+    /// let targets = exec.stop_and_collect(Duration::from_secs(1));
+    /// let mut exec = AsyncTargetExecutor::new().set_max_concurrent_checks(4);
+    /// exec.start(targets).unwrap();
+    /// ```
+    ///
+    /// # Notes
+    /// If the executor was started on an existing runtime and this is called from within a
+    /// runtime, all check tasks are signaled to terminate, but not waited for. No targets are
+    /// returned in this case. Use [AsyncTargetExecutor::stop_and_collect_async] instead.
+    pub fn stop_and_collect(&mut self, timeout: Duration) -> Vec<AsyncTarget<'static>> {
+        if self.handle.take().is_none() {
+            return Vec::new();
+        }
+
+        let tasks = self.teardown(Instant::now() + timeout);
+        let targets = self.join(tasks);
+        self.statuses.write().unwrap().clear();
+        targets
+    }
+
+    /// Stop asynchronous processing gracefully from within an async context. The returned
+    /// future completes once all check tasks terminated. In-flight checks are abandoned.
+    pub async fn stop_async(&mut self) {
+        self.stop_with_timeout_async(Duration::ZERO).await;
+    }
+
+    /// Stop asynchronous processing gracefully from within an async context, waiting for
+    /// in-flight checks up to given timeout. See [AsyncTargetExecutor::stop_with_timeout].
+    pub async fn stop_with_timeout_async(&mut self, timeout: Duration) -> HashMap<String, Status> {
+        if self.handle.take().is_none() {
+            return HashMap::new();
+        }
+
+        let ids = self.running.iter().map(|running| running.id.clone()).collect();
+        let tasks = self.teardown(Instant::now() + timeout);
+        self.join_async(tasks).await;
+        self.final_statuses(ids)
+    }
+
+    /// Stop asynchronous processing gracefully from within an async context and hand back all
+    /// checked targets. See [AsyncTargetExecutor::stop_and_collect].
+    pub async fn stop_and_collect_async(&mut self, timeout: Duration) -> Vec<AsyncTarget<'static>> {
+        if self.handle.take().is_none() {
+            return Vec::new();
+        }
+
+        let tasks = self.teardown(Instant::now() + timeout);
+        let targets = self.join_async(tasks).await;
+        self.statuses.write().unwrap().clear();
+        targets
+    }
+
+    /// Signal all check tasks to terminate. In-flight checks may finish until given deadline.
+    ///
+    /// # Returns
+    /// The handles of all signaled tasks.
+    fn teardown(&mut self, deadline: Instant) -> Vec<JoinHandle<SharedTarget>> {
+        self.running
+            .drain(..)
+            .map(|running| {
+                let _ = running.teardown_send.send(deadline);
+                running.task
+            })
+            .collect()
+    }
+
+    /// Wait until given tasks terminated and shutdown the owned runtime, if any.
+    ///
+    /// # Returns
+    /// The targets of all tasks. Empty, if the tasks could not be waited for.
+    fn join(&mut self, tasks: Vec<JoinHandle<SharedTarget>>) -> Vec<AsyncTarget<'static>> {
+        let targets = match self.runtime.take() {
+            Some(runtime) => {
+                let targets = block_on(join_all(tasks));
+                AsyncTargetExecutor::shutdown(runtime);
+                targets
+            }
+            None if Handle::try_current().is_err() => block_on(join_all(tasks)),
+            None => Vec::new(),
+        };
+        targets.into_iter().filter_map(Result::ok).map(reclaim).collect()
+    }
+
+    /// Wait until given tasks terminated from within an async context and shutdown the owned
+    /// runtime, if any.
+    ///
+    /// # Returns
+    /// The targets of all tasks.
+    async fn join_async(&mut self, tasks: Vec<JoinHandle<SharedTarget>>) -> Vec<AsyncTarget<'static>> {
+        let targets = join_all(tasks).await;
+        if let Some(runtime) = self.runtime.take() {
+            AsyncTargetExecutor::shutdown(runtime);
+        }
+        targets.into_iter().filter_map(Result::ok).map(reclaim).collect()
+    }
+
+    /// Get the confirmed status of all targets with given ids and forget all statuses.
+    fn final_statuses(&self, ids: Vec<String>) -> HashMap<String, Status> {
+        let mut statuses = self.statuses.write().unwrap();
+        let final_statuses = ids
+            .into_iter()
+            .map(|id| {
+                let status = statuses.get(&id).cloned().unwrap_or(Status::Unknown);
+                (id, status)
+            })
+            .collect();
+        statuses.clear();
+        final_statuses
+    }
+
+    /// Shutdown the runtime owned by this executor.
+    fn shutdown(runtime: Runtime) {
+        // Note: Some async calls were offloaded to dedicated processing threads. For a
+        // runtime to shutdown, these threads must have been processed, this
+        // causes potentially a huge delay. To prevent this, the runtime is shutdown in
+        // background allowing this call to terminate in a timely manner.
+        runtime.shutdown_background();
+    }
+
+    /// Spawn the periodic availability check task of given target on the runtime.
+    fn spawn(&mut self, target: AsyncTarget<'static>) {
+        if let Some(handle) = &self.handle {
+            let target_fingerprint = target.fingerprint.clone();
+            let (teardown_send, teardown_recv) = oneshot::channel();
+            let (control_send, control_recv) = mpsc::unbounded_channel();
+            let id = target.get_id();
+            let task = check_target_periodically(
+                target,
+                id.clone(),
+                self.observers.clone(),
+                self.statuses.clone(),
+                self.scheduling.clone(),
+                teardown_recv,
+                control_recv,
+            );
+            self.running.push(RunningTarget {
+                id,
+                fingerprint: target_fingerprint,
+                teardown_send,
+                control_send,
+                task: handle.spawn(task),
+            });
+        }
+    }
+}
+
+impl Default for AsyncTargetExecutor {
+    fn default() -> Self {
+        AsyncTargetExecutor::new()
+    }
+}
+
+impl Drop for AsyncTargetExecutor {
+    fn drop(&mut self) {
+        self.stop()
+    }
+}
+
+/// Snapshot of the self-metrics of an [AsyncTargetExecutor]. See [AsyncTargetExecutor::get_metrics].
+#[derive(Clone, Debug, Default)]
+pub struct ExecutorMetrics {
+    in_flight_checks: usize,
+    blocking_checks: usize,
+    max_blocking_threads: Option<usize>,
+    targets: HashMap<String, TargetMetrics>,
+}
+
+impl ExecutorMetrics {
+    /// Get the number of checks currently performed, including checks waiting for the executor-wide
+    /// limits. See [AsyncTargetExecutor::set_max_concurrent_checks].
+    pub fn get_in_flight_checks(&self) -> usize {
+        self.in_flight_checks
+    }
+
+    /// Get the number of checks occupying or waiting for a thread of the blocking pool. Stuck
+    /// checks exceeding their timeout keep occupying their thread.
+    pub fn get_blocking_checks(&self) -> usize {
+        self.blocking_checks
+    }
+
+    /// Get the maximum number of threads of the blocking pool. None, if the executor was started
+    /// on an existing runtime. See [AsyncTargetExecutor::start_on].
+    pub fn get_max_blocking_threads(&self) -> Option<usize> {
+        self.max_blocking_threads
+    }
+
+    /// Get the saturation of the blocking pool, e.g. 0.5 if half of its threads are occupied by
+    /// checks. Values above 1.0 indicate checks waiting for a thread.
+    pub fn get_blocking_saturation(&self) -> Option<f64> {
+        self.max_blocking_threads
+            .map(|max_blocking_threads| self.blocking_checks as f64 / max_blocking_threads as f64)
+    }
+
+    /// Get the metrics of the target with given id.
+    pub fn get_target(&self, id: &str) -> Option<&TargetMetrics> {
+        self.targets.get(id)
+    }
+
+    /// Get the metrics of all targets by id.
+    pub fn get_targets(&self) -> &HashMap<String, TargetMetrics> {
+        &self.targets
+    }
+}
+
+/// Self-metrics of the checks of a single target. See [AsyncTargetExecutor::get_metrics].
+#[derive(Clone, Debug, Default)]
+pub struct TargetMetrics {
+    check_duration: DurationHistogram,
+    scheduling_lag: DurationHistogram,
+}
+
+impl TargetMetrics {
+    /// Get the durations of all checks, including all retries.
+    pub fn get_check_duration(&self) -> &DurationHistogram {
+        &self.check_duration
+    }
+
+    /// Get the scheduling lags of all checks: The time between the intended and the actual start
+    /// of a check. Lags are caused by the executor-wide limits or an overloaded runtime.
+    pub fn get_scheduling_lag(&self) -> &DurationHistogram {
+        &self.scheduling_lag
+    }
+}
+
+/// Histogram of observed durations with fixed buckets from 5ms to 10s.
+#[derive(Clone, Debug, Default)]
+pub struct DurationHistogram {
+    /// Number of observations per bucket. The last entry counts observations above all buckets.
+    counts: [u64; HISTOGRAM_BUCKETS.len() + 1],
+    sum: Duration,
+}
+
+impl DurationHistogram {
+    /// Get the number of all observations.
+    pub fn get_count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Get the sum of all observed durations.
+    pub fn get_sum(&self) -> &Duration {
+        &self.sum
+    }
+
+    /// Get all buckets as pairs of upper bound and cumulative number of observations less than or
+    /// equal to the upper bound, like a Prometheus histogram.
+    pub fn get_buckets(&self) -> Vec<(Duration, u64)> {
+        HISTOGRAM_BUCKETS
+            .iter()
+            .zip(self.counts.iter())
+            .scan(0, |cumulative, (bound, count)| {
+                *cumulative += count;
+                Some((Duration::from_millis(*bound), *cumulative))
+            })
+            .collect()
+    }
+
+    /// Add given duration to the histogram.
+    fn observe(&mut self, duration: Duration) {
+        let index = HISTOGRAM_BUCKETS
+            .iter()
+            .position(|bound| duration <= Duration::from_millis(*bound))
+            .unwrap_or(HISTOGRAM_BUCKETS.len());
+        self.counts[index] += 1;
+        self.sum += duration;
+    }
+}
+
+/// Stand-in for a [Target], whose check is still running after its deadline expired.
+struct StalledTarget {
+    id: String,
+}
+
+impl Target for StalledTarget {
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn check_availability(&self) -> Result<Status, CheckTargetError> {
+        Err(CheckTargetError::from("Previous check still running"))
+    }
+}
+
+/// Check the availability of given target. Retry on anything other than [Status::Available]
+/// or [Status::Degraded] until all retries were used up.
+///
+/// # Notes
+/// Fails immediately, if a previous check of the target is still running. A panic during a check
+/// is caught and reported as [CheckTargetError::Panic].
+fn check_availability(target: &Mutex<BoxedTarget>, retries: u32) -> (Status, Option<CheckTargetError>) {
+    let target = match try_lock(target) {
+        Some(target) => target,
+        None => {
+            return (
+                Status::Unknown,
+                Some(CheckTargetError::from("Previous check still running")),
+            )
+        }
+    };
+
+    let mut attempts = 0;
+    loop {
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| target.check_availability())) {
+            Ok(Ok(status)) => (status, None),
+            Ok(Err(error)) => (Status::Unknown, Some(error)),
+            Err(payload) => (Status::Unknown, Some(CheckTargetError::Panic(panic_message(payload)))),
+        };
+
+        attempts += 1;
+        let reachable = matches!(result.0, Status::Available | Status::Degraded(_));
+        if reachable || attempts > retries {
+            return result;
+        }
+    }
+}
+
+async fn check_target_periodically(
+    target: AsyncTarget<'static>,
+    id: String,
+    observers: Arc<RwLock<Vec<SharedObserver>>>,
+    statuses: Arc<Statuses>,
+    scheduling: Scheduling,
+    mut teardown_recv: Receiver<Instant>,
+    mut control_recv: UnboundedReceiver<Control>,
+) -> SharedTarget {
+    // Note: Scheduled targets remember the last point in time they were due at. Checks may begin
+    // slightly early due to differences between monotonic and system clock.
+    let mut scheduled = SystemTime::now();
+    let mut next_check = match (target.next_scheduled(scheduled), scheduling.spread_start) {
+        (Some(time), _) => {
+            scheduled = time;
+            instant_at(time)
+        }
+        (None, true) => Instant::now() + random_duration(*target.get_check_interval()),
+        (None, false) => Instant::now(),
+    };
+    let mut last_check = Instant::now();
+    let mut paused = false;
+    let mut replies: Vec<Sender<Status>> = Vec::new();
+
+    let target = Arc::new(Mutex::new(target));
+    loop {
+        let check_due = !paused || !replies.is_empty();
+        select! {
+            // Next check is due. Perform it, unless a teardown message is received meanwhile.
+            _ = time::sleep_until(time::Instant::from_std(next_check)), if check_due => {
+                let check = check_target(&target, &id, &observers, &statuses, &scheduling, next_check);
+                tokio::pin!(check);
+                select! {
+                    begun = &mut check => last_check = begun,
+                    // Note: The in-flight check may finish until the deadline. If the executor is
+                    // gone, the check is abandoned immediately.
+                    deadline = &mut teardown_recv => {
+                        let deadline = deadline.unwrap_or_else(|_| Instant::now());
+                        let _ = time::timeout_at(time::Instant::from_std(deadline), check).await;
+                        break;
+                    }
+                };
+
+                let (interval, next_scheduled, status) = {
+                    let target = lock(&target);
+                    let next_scheduled = target.next_scheduled(SystemTime::now().max(scheduled));
+                    (target.next_interval(), next_scheduled, target.status.clone())
+                };
+                next_check = match next_scheduled {
+                    Some(time) => {
+                        scheduled = time;
+                        instant_at(time)
+                    }
+                    None => {
+                        let jitter = scheduling.jitter.map(random_duration).unwrap_or_default();
+                        last_check + interval + jitter
+                    }
+                };
+                replies.drain(..).for_each(|reply| {
+                    let _ = reply.send(status.clone());
+                });
+            }
+
+            // Control message was received. Adjust schedule.
+            control = control_recv.recv() => match control {
+                Some(Control::Pause) => paused = true,
+                Some(Control::Resume) => paused = false,
+                Some(Control::CheckNow(reply)) => {
+                    replies.push(reply);
+                    next_check = Instant::now();
+                }
+                Some(Control::SetInterval(interval_policy)) => {
+                    let mut target = lock(&target);
+                    target.interval_policy = interval_policy;
+                    target.schedule = None;
+                    next_check = last_check + target.next_interval();
+                }
+                None => break,
+            },
+
+            // Teardown message was received or the executor is gone: Stop processing
+            _ = &mut teardown_recv => break,
+        };
+    }
+    target
+}
+
+/// Convert a point in time into an [Instant]. Points in time in the past are converted to now.
+fn instant_at(time: SystemTime) -> Instant {
+    Instant::now() + time.duration_since(SystemTime::now()).unwrap_or_default()
+}
+
+async fn check_target(
+    target: &Arc<Mutex<AsyncTarget<'static>>>,
+    id: &str,
+    observers: &Arc<RwLock<Vec<SharedObserver>>>,
+    statuses: &Arc<Statuses>,
+    scheduling: &Scheduling,
+    due: Instant,
+) -> Instant {
+    let (check_timeout, checked, retries) = {
+        let target = lock(target);
+        (target.check_timeout, target.target.clone(), target.retries)
+    };
+    let _in_flight = Occupation::new(&scheduling.metrics.in_flight_checks);
+    let begun = Instant::now();
+
+    // Wait for the executor-wide limits. The permit is held until the check returned.
+    let permit = scheduling.acquire().await;
+
+    // Note: The result of a check is processed by either the check itself or, after the
+    // deadline expired, by a timeout. Whoever claims the check first, reports it.
+    let claimed = Arc::new(AtomicBool::new(false));
+    let started = Instant::now();
+    scheduling.metrics.record(id, |metrics| {
+        metrics.scheduling_lag.observe(started.saturating_duration_since(due))
+    });
+
+    // Offload potentially blocking check_availability call onto a separate thread
+    // Note: Checks refer to their target weakly. The result of a check outliving the periodic
+    // check task, e.g. after the executor stopped, is discarded.
+    let mut check = task::spawn_blocking({
+        let (target, id, claimed) = (Arc::downgrade(target), id.to_string(), claimed.clone());
+        let (observers, statuses) = (observers.clone(), statuses.clone());
+        let (metrics, blocking) = (
+            scheduling.metrics.clone(),
+            Occupation::new(&scheduling.metrics.blocking_checks),
+        );
+        move || {
+            let (status, error) = check_availability(&checked, retries);
+            metrics.record(&id, |metrics| metrics.check_duration.observe(started.elapsed()));
+            drop(blocking);
+            drop(permit);
+            if let (false, Some(target)) = (claimed.swap(true, Ordering::SeqCst), target.upgrade()) {
+                process_check(&target, &id, &observers, &statuses, status, error, started.elapsed());
+            }
+        }
+    });
+
+    let timed_out = match check_timeout {
+        Some(check_timeout) => time::timeout(check_timeout, &mut check).await.is_err(),
+        None => false,
+    };
+
+    // Note: A stuck check is not waited for, it is left running in background.
+    if !timed_out {
+        let _ = check.await;
+    } else if !claimed.swap(true, Ordering::SeqCst) {
+        let (target, id) = (Arc::downgrade(target), id.to_string());
+        let (observers, statuses) = (observers.clone(), statuses.clone());
+        let duration = started.elapsed();
+        let _ = task::spawn_blocking(move || {
+            if let Some(target) = target.upgrade() {
+                let error = CheckTargetError::from("Check timed out");
+                process_check(
+                    &target,
+                    &id,
+                    &observers,
+                    &statuses,
+                    Status::Unknown,
+                    Some(error),
+                    duration,
+                );
+            }
+        })
+        .await;
+    } else {
+        let _ = check.await;
+    }
+
+    begun
+}
+
+/// Verify the ids and dependencies of given targets: Each id must be unique, all parents must be
+/// part of the targets and must not depend on their children, neither directly nor transitively.
+pub(crate) fn verify_dependencies(targets: &[AsyncTarget]) -> Result<(), DependencyError> {
+    let ids: Vec<String> = targets.iter().map(|target| target.get_id()).collect();
+    let mut parents: HashMap<&str, &[String]> = HashMap::with_capacity(ids.len());
+    for (id, target) in ids.iter().zip(targets.iter()) {
+        if parents.insert(id.as_str(), target.parents.as_slice()).is_some() {
+            return Err(DependencyError::DuplicateId(id.clone()));
+        }
+    }
+
+    let mut verified = HashSet::new();
+    for id in ids.iter() {
+        visit_parents(id, &parents, &mut Vec::new(), &mut verified)?;
+    }
+    Ok(())
+}
+
+/// Visit all parents of given target depth first. The path contains all targets currently visited.
+fn visit_parents<'a>(
+    id: &'a str,
+    parents: &HashMap<&'a str, &'a [String]>,
+    path: &mut Vec<&'a str>,
+    verified: &mut HashSet<&'a str>,
+) -> Result<(), DependencyError> {
+    if verified.contains(id) {
+        return Ok(());
+    }
+    if let Some(index) = path.iter().position(|visited| *visited == id) {
+        let mut cycle: Vec<String> = path[index..].iter().map(|visited| String::from(*visited)).collect();
+        cycle.push(String::from(id));
+        return Err(DependencyError::Cycle(cycle));
+    }
+
+    path.push(id);
+    for parent in parents[id].iter() {
+        if !parents.contains_key(parent.as_str()) {
+            return Err(DependencyError::UnknownParent(String::from(id), parent.clone()));
+        }
+        visit_parents(parent, parents, path, verified)?;
+    }
+    path.pop();
+    verified.insert(id);
+    Ok(())
+}
+
+/// Get a random [Duration] between zero and given maximum.
+fn random_duration(max: Duration) -> Duration {
+    // Note: Each RandomState is randomly seeded, providing sufficient randomness for scheduling.
+    let random = RandomState::new().build_hasher().finish();
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+/// Process the result of a check: Update the stored status, notify all observers and call
+/// the check handler.
+fn process_check(
+    target: &Mutex<AsyncTarget>,
+    id: &str,
+    observers: &RwLock<Vec<SharedObserver>>,
+    statuses: &Statuses,
+    status: Status,
+    error: Option<CheckTargetError>,
+    duration: Duration,
+) {
+    let mut target = lock(target);
+    let reachable = matches!(status, Status::Available | Status::Degraded(_));
+    let (status, old_status) = target.update_status(status);
+    let timestamp = SystemTime::now();
+    let maintenance = target.in_maintenance(timestamp);
+    let was_flapping = target.is_flapping();
+    let flapping_stopped = target.update_flapping(&status, &old_status, Instant::now());
+    let flapping = target.is_flapping();
+
+    // Lookup a parent causing the failed check and publish the status for the children
+    let unreachable_parent = match reachable {
+        true => None,
+        false => {
+            let statuses = statuses.read().unwrap();
+            target
+                .parents
+                .iter()
+                .find(|parent| {
+                    statuses
+                        .get(*parent)
+                        .is_some_and(|status| !matches!(status, Status::Available | Status::Degraded(_)))
+                })
+                .cloned()
+        }
+    };
+    statuses.write().unwrap().insert(String::from(id), status.clone());
+
+    // Notify observers
+    let report = CheckReport {
+        id,
+        status: &status,
+        old_status: &old_status,
+        error: error.as_ref(),
+        duration,
+        timestamp,
+        maintenance,
+        unreachable_parent: unreachable_parent.as_deref(),
+        flapping,
+    };
+    for observer in observers.read().unwrap().iter() {
+        observer.on_check(&report);
+        if flapping != was_flapping {
+            observer.on_flapping(&report);
+        }
+    }
+
+    // Call stored Handler, unless the target is in maintenance or flapping. A stuck check still
+    // holds the target.
+    if maintenance || flapping {
+        return;
+    }
+    let old_status = flapping_stopped.unwrap_or(old_status);
+    let error = match unreachable_parent {
+        Some(parent) => Some(CheckTargetError::UnreachableParent(parent)),
+        None => error,
+    };
+    let target = &mut *target;
+    let handler = target.check_handler.as_mut();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| match try_lock(&target.target) {
+        Some(checked) => handler(checked.as_ref(), status, old_status, error),
+        None => handler(&StalledTarget { id: id.to_string() }, status, old_status, error),
+    }));
+
+    // Note: A panicking handler must not stop the checks of the target. Report it instead.
+    if let Err(payload) = result {
+        let message = panic_message(payload);
+        for observer in observers.read().unwrap().iter() {
+            observer.on_handler_panic(id, &message);
+        }
+    }
+}
+
+/// Lock given mutex. A mutex poisoned by a panic is recovered, the panic was handled already.
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Try to lock given mutex without blocking. A mutex poisoned by a panic is recovered.
+fn try_lock<T: ?Sized>(mutex: &Mutex<T>) -> Option<MutexGuard<'_, T>> {
+    match mutex.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+/// Take back a shared target from its checks. Waits until a check currently processing its
+/// result released the target.
+fn reclaim(target: SharedTarget) -> AsyncTarget<'static> {
+    let mut target = target;
+    loop {
+        match Arc::try_unwrap(target) {
+            Ok(target) => return target.into_inner().unwrap_or_else(PoisonError::into_inner),
+            Err(shared) => {
+                drop(lock(&shared));
+                std::thread::yield_now();
+                target = shared;
+            }
+        }
+    }
+}
+
+/// Get the message of a caught panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast_ref::<&str>() {
+            Some(message) => String::from(*message),
+            None => String::from("Unknown panic"),
+        },
+    }
+}
+
+#[cfg(test)]
 mod tests {
+    use std::sync::atomic::AtomicUsize;
     use std::sync::mpsc;
+    use std::thread::sleep;
 
     use mockall::Sequence;
 
@@ -225,6 +1726,7 @@ mod tests {
         // Prepare Mock
         let mut mock = MockTarget::new();
         let mut call_sequence = Sequence::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
 
         // First call: return Status::Available
         mock.expect_check_availability()
@@ -251,28 +1753,789 @@ mod tests {
                 // Verify expectency of the first call to check_availability
                 Status::Unknown => {
                     assert_eq!(new, Status::Available);
-                    assert_eq!(error.is_none(), true);
+                    assert!(error.is_none());
                 }
                 // Verify expectency of the second call to check_availability
                 Status::Available => {
                     assert_eq!(new, Status::NotAvailable);
-                    assert_eq!(error.is_none(), true);
+                    assert!(error.is_none());
                 }
                 // Verify expectency of the third call to check_availability. Stop handler.
                 Status::NotAvailable => {
                     assert_eq!(new, Status::Unknown);
-                    assert_eq!(error.is_some(), true);
+                    assert!(error.is_some());
                     let error = error.unwrap();
                     assert_eq!(format!("{}", error), "Error");
                     send.send(()).unwrap();
                 }
+                Status::Degraded(_) => unreachable!(),
             }
         };
 
         // Run test
         let mut exec = AsyncTargetExecutor::new();
-        exec.start(vec![AsyncTarget::from((mock, handler, Duration::from_millis(100)))]);
+        exec.start(vec![AsyncTarget::from((mock, handler, Duration::from_millis(100)))])
+            .unwrap();
         recv.recv().unwrap();
         exec.stop();
     }
+
+    #[test]
+    fn async_target_retries() {
+        // Expectency: A check is retried as long as the target is not available and retries are left.
+        let mut mock = MockTarget::new();
+        let mut call_sequence = Sequence::new();
+        mock.expect_check_availability()
+            .times(2)
+            .returning(|| Ok(Status::NotAvailable))
+            .in_sequence(&mut call_sequence);
+        mock.expect_check_availability()
+            .times(1)
+            .returning(|| Ok(Status::Available))
+            .in_sequence(&mut call_sequence);
+
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+        let target = AsyncTarget::from((mock, handler, Duration::from_secs(1))).set_retries(2);
+        let (status, error) = check_availability(&target.target, target.retries);
+        assert_eq!(status, Status::Available);
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn async_target_check_timeout() {
+        // Expectency: A check exceeding its deadline is reported as Status::Unknown with an error,
+        //             without waiting for it. Following checks fail until the stuck check returned.
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().times(1).returning(|| {
+            sleep(Duration::from_millis(400));
+            Ok(Status::Available)
+        });
+
+        let (send, recv) = mpsc::channel();
+        let handler = move |target: &dyn Target, new: Status, _: OldStatus, error: Option<CheckTargetError>| {
+            let _ = send.send((target.get_id(), new, error.map(|error| error.to_string())));
+        };
+        let target =
+            AsyncTarget::from((mock, handler, Duration::from_millis(100))).set_check_timeout(Duration::from_millis(50));
+
+        let mut exec = AsyncTargetExecutor::new();
+        exec.start(vec![target]).unwrap();
+        let first = recv.recv_timeout(Duration::from_millis(300)).unwrap();
+        let second = recv.recv_timeout(Duration::from_millis(300)).unwrap();
+        exec.stop();
+
+        let timed_out = Some(String::from("Check timed out"));
+        let still_running = Some(String::from("Previous check still running"));
+        assert_eq!(first, (String::from("mock"), Status::Unknown, timed_out));
+        assert_eq!(second, (String::from("mock"), Status::Unknown, still_running));
+    }
+
+    #[test]
+    fn async_target_executor_max_concurrent_checks() {
+        // Expectency: No more checks than configured are running at the same time.
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let targets = (0..4)
+            .map(|index| {
+                let (running, peak) = (running.clone(), peak.clone());
+                let mut mock = MockTarget::new();
+                mock.expect_get_id().returning(move || index.to_string());
+                mock.expect_check_availability().returning(move || {
+                    peak.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                    sleep(Duration::from_millis(50));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(Status::Available)
+                });
+                let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+                AsyncTarget::from((mock, handler, Duration::from_millis(10)))
+            })
+            .collect();
+
+        let mut exec = AsyncTargetExecutor::new().set_max_concurrent_checks(2);
+        exec.start(targets).unwrap();
+        sleep(Duration::from_millis(300));
+        exec.stop();
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn async_target_executor_rate_limit() {
+        // Expectency: Checks of all targets are started no faster than the configured rate.
+        let checks = Arc::new(AtomicUsize::new(0));
+        let targets = (0..4)
+            .map(|index| {
+                let checks = checks.clone();
+                let mut mock = MockTarget::new();
+                mock.expect_get_id().returning(move || index.to_string());
+                mock.expect_check_availability().returning(move || {
+                    checks.fetch_add(1, Ordering::SeqCst);
+                    Ok(Status::Available)
+                });
+                let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+                AsyncTarget::from((mock, handler, Duration::from_millis(10)))
+            })
+            .collect();
+
+        let mut exec = AsyncTargetExecutor::new()
+            .set_rate_limit(20)
+            .set_jitter(Duration::from_millis(5))
+            .set_spread_start(true);
+        exec.start(targets).unwrap();
+        sleep(Duration::from_millis(500));
+        exec.stop();
+
+        // Note: 20 checks per second allow up to 11 checks within 500ms
+        let checks = checks.load(Ordering::SeqCst);
+        assert!((5..=11).contains(&checks), "{} checks", checks);
+    }
+
+    #[test]
+    fn interval_policy() {
+        // Expectency: Reachable targets use the healthy interval, failing targets the failing
+        //             interval. While not available, the interval backs off up to its maximum.
+        let policy = IntervalPolicy::new(Duration::from_secs(60))
+            .set_failing_interval(Duration::from_secs(5))
+            .set_backoff(2.0, Duration::from_secs(30));
+
+        let degraded = Status::Degraded(String::from("slow"));
+        assert_eq!(policy.interval(&Status::Available, 0), Duration::from_secs(60));
+        assert_eq!(policy.interval(&degraded, 0), Duration::from_secs(60));
+        assert_eq!(policy.interval(&Status::Unknown, 0), Duration::from_secs(5));
+        assert_eq!(policy.interval(&Status::NotAvailable, 1), Duration::from_secs(5));
+        assert_eq!(policy.interval(&Status::NotAvailable, 2), Duration::from_secs(10));
+        assert_eq!(policy.interval(&Status::NotAvailable, 3), Duration::from_secs(20));
+        assert_eq!(policy.interval(&Status::NotAvailable, 4), Duration::from_secs(30));
+        assert_eq!(
+            policy.interval(&Status::NotAvailable, u32::MAX),
+            Duration::from_secs(30)
+        );
+
+        // Without backoff, the failing interval is kept
+        let policy = IntervalPolicy::from(Duration::from_secs(60)).set_failing_interval(Duration::from_secs(5));
+        assert_eq!(policy.interval(&Status::NotAvailable, 10), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn async_target_next_interval() {
+        // Expectency: The next interval follows the raw check results, regardless of hysteresis.
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+        let policy = IntervalPolicy::new(Duration::from_secs(60))
+            .set_failing_interval(Duration::from_secs(5))
+            .set_backoff(3.0, Duration::from_secs(600));
+        let mut target = AsyncTarget::from((MockTarget::new(), handler, Duration::from_secs(1)))
+            .set_interval_policy(policy)
+            .set_hysteresis(3);
+        assert_eq!(target.get_check_interval(), &Duration::from_secs(60));
+
+        target.update_status(Status::Available);
+        assert_eq!(target.next_interval(), Duration::from_secs(60));
+        target.update_status(Status::NotAvailable);
+        assert_eq!(target.next_interval(), Duration::from_secs(5));
+        target.update_status(Status::NotAvailable);
+        assert_eq!(target.next_interval(), Duration::from_secs(15));
+        target.update_status(Status::Available);
+        assert_eq!(target.next_interval(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn async_target_executor_start_on() {
+        // Expectency: Checks run on a given runtime, even a single threaded one, and are stopped
+        //             gracefully from within this runtime.
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().returning(|| Ok(Status::Available));
+
+        let (send, mut recv) = tokio::sync::mpsc::unbounded_channel();
+        let handler = move |_: &dyn Target, new: Status, _: OldStatus, _: Option<CheckTargetError>| {
+            let _ = send.send(new);
+        };
+
+        let runtime = runtime::Builder::new_current_thread().enable_time().build().unwrap();
+        runtime.block_on(async {
+            let mut exec = AsyncTargetExecutor::new();
+            exec.start_on(
+                Handle::current(),
+                vec![AsyncTarget::from((mock, handler, Duration::from_millis(10)))],
+            )
+            .unwrap();
+            assert_eq!(recv.recv().await, Some(Status::Available));
+            assert_eq!(recv.recv().await, Some(Status::Available));
+            exec.stop_async().await;
+
+            // Note: The handler is dropped with the stopped check task
+            while recv.recv().await.is_some() {}
+        });
+    }
+
+    #[test]
+    fn async_target_executor_controls() {
+        // Expectency: Paused targets are not checked periodically, but on demand. After resuming,
+        //             the target is checked with the updated interval again.
+        let checks = Arc::new(AtomicUsize::new(0));
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().returning({
+            let checks = checks.clone();
+            move || {
+                checks.fetch_add(1, Ordering::SeqCst);
+                Ok(Status::Available)
+            }
+        });
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+
+        let mut exec = AsyncTargetExecutor::new();
+        exec.start(vec![AsyncTarget::from((mock, handler, Duration::from_secs(60)))])
+            .unwrap();
+
+        // Unknown targets can not be controlled
+        assert!(!exec.pause("unknown"));
+        assert_eq!(block_on(exec.check_now("unknown")), None);
+
+        // Check on demand instead of waiting for the interval
+        sleep(Duration::from_millis(50));
+        assert_eq!(block_on(exec.check_now("mock")), Some(Status::Available));
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+
+        // No periodic checks while paused, except on demand
+        assert!(exec.pause("mock"));
+        assert!(exec.set_interval("mock", Duration::from_millis(10)));
+        sleep(Duration::from_millis(100));
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+        assert_eq!(block_on(exec.check_now("mock")), Some(Status::Available));
+        assert_eq!(checks.load(Ordering::SeqCst), 3);
+
+        // Resumed targets are checked with the new interval
+        assert!(exec.resume("mock"));
+        sleep(Duration::from_millis(200));
+        exec.stop();
+        assert!(checks.load(Ordering::SeqCst) > 5);
+    }
+
+    #[test]
+    fn async_target_executor_stop_with_timeout() {
+        // Expectency: Stopping waits for in-flight checks up to the timeout and returns the final
+        //             status of all targets. Checks exceeding the timeout are abandoned.
+        let slow_target = |id: &'static str, delay: u64, status: Status| {
+            let mut mock = MockTarget::new();
+            mock.expect_get_id().returning(move || String::from(id));
+            mock.expect_check_availability().returning(move || {
+                sleep(Duration::from_millis(delay));
+                Ok(status.clone())
+            });
+            let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+            AsyncTarget::from((mock, handler, Duration::from_secs(60)))
+        };
+
+        // Not started executors have no final status
+        let mut exec = AsyncTargetExecutor::new();
+        assert!(exec.stop_with_timeout(Duration::from_secs(1)).is_empty());
+
+        // In-flight checks finishing before the timeout are reported
+        exec.start(vec![
+            slow_target("fast", 0, Status::NotAvailable),
+            slow_target("slow", 200, Status::Available),
+        ])
+        .unwrap();
+        sleep(Duration::from_millis(50));
+        let statuses = exec.stop_with_timeout(Duration::from_secs(1));
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses["fast"], Status::NotAvailable);
+        assert_eq!(statuses["slow"], Status::Available);
+
+        // In-flight checks exceeding the timeout are abandoned
+        exec.start(vec![slow_target("slow", 1000, Status::Available)]).unwrap();
+        sleep(Duration::from_millis(50));
+        let stopping = Instant::now();
+        let statuses = exec.stop_with_timeout(Duration::from_millis(50));
+        assert!(stopping.elapsed() < Duration::from_millis(500));
+        assert_eq!(statuses["slow"], Status::Unknown);
+
+        // Async variant
+        let runtime = runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let statuses = runtime.block_on(async {
+            let mut exec = AsyncTargetExecutor::new();
+            exec.start_on(Handle::current(), vec![slow_target("slow", 200, Status::Available)])
+                .unwrap();
+            time::sleep(Duration::from_millis(50)).await;
+            exec.stop_with_timeout_async(Duration::from_secs(1)).await
+        });
+        assert_eq!(statuses["slow"], Status::Available);
+    }
+
+    #[test]
+    fn async_target_executor_stop_and_collect() {
+        // Expectency: Stopped targets are handed back with their handler and status and can be
+        //             started again on another executor.
+        let checks = Arc::new(AtomicUsize::new(0));
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().returning(|| Ok(Status::Available));
+        let handler = {
+            let checks = checks.clone();
+            move |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {
+                checks.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+        let target = AsyncTarget::from((mock, handler, Duration::from_secs(60))).set_retries(2);
+
+        // Not started executors have no targets
+        let mut exec = AsyncTargetExecutor::new();
+        assert!(exec.stop_and_collect(Duration::from_secs(1)).is_empty());
+
+        exec.start(vec![target]).unwrap();
+        sleep(Duration::from_millis(50));
+        let targets = exec.stop_and_collect(Duration::from_secs(1));
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].get_id(), "mock");
+        assert_eq!(targets[0].get_status(), &Status::Available);
+        assert_eq!(targets[0].get_retries(), 2);
+        assert_eq!(checks.load(Ordering::SeqCst), 1);
+
+        // Restarted targets keep their handler
+        let runtime = runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let targets = runtime.block_on(async {
+            let mut exec = AsyncTargetExecutor::new().set_max_concurrent_checks(1);
+            exec.start_on(Handle::current(), targets).unwrap();
+            time::sleep(Duration::from_millis(50)).await;
+            exec.stop_and_collect_async(Duration::from_secs(1)).await
+        });
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].get_status(), &Status::Available);
+        assert_eq!(checks.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn async_target_executor_metrics() {
+        // Expectency: The executor tracks the checks of all targets: Check durations, scheduling
+        //             lags, in-flight checks and the occupation of the blocking pool.
+        let (entered_send, entered_recv) = mpsc::channel();
+        let (done_send, done_recv) = mpsc::channel();
+        let mut releases = HashMap::new();
+        let mut gated_target = |id: &'static str| {
+            // Note: Each check blocks until it is released by the test
+            let (release_send, release_recv) = mpsc::channel::<()>();
+            releases.insert(id, release_send);
+            let entered_send = entered_send.clone();
+            let mut mock = MockTarget::new();
+            mock.expect_get_id().returning(move || String::from(id));
+            mock.expect_check_availability().returning(move || {
+                entered_send.send(id).unwrap();
+                release_recv.recv().unwrap();
+                Ok(Status::Available)
+            });
+            let done_send = Mutex::new(done_send.clone());
+            let handler = move |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {
+                done_send.lock().unwrap().send(id).unwrap();
+            };
+            AsyncTarget::from((mock, handler, Duration::from_secs(3600)))
+        };
+        let targets = vec![gated_target("a"), gated_target("b")];
+        let timeout = Duration::from_secs(5);
+
+        // Only one check at a time, the second target waits for the first one
+        let mut exec = AsyncTargetExecutor::new().set_max_concurrent_checks(1);
+        exec.start(targets).unwrap();
+        let first = entered_recv.recv_timeout(timeout).unwrap();
+        let entered = Instant::now();
+        while exec.get_metrics().get_in_flight_checks() < 2 {
+            assert!(entered.elapsed() < timeout);
+            std::thread::yield_now();
+        }
+        let metrics = exec.get_metrics();
+        assert_eq!(metrics.get_in_flight_checks(), 2);
+        assert_eq!(metrics.get_blocking_checks(), 1);
+        assert_eq!(metrics.get_max_blocking_threads(), Some(MAX_BLOCKING_THREADS));
+        assert_eq!(
+            metrics.get_blocking_saturation(),
+            Some(1.0 / MAX_BLOCKING_THREADS as f64)
+        );
+
+        // Release the first check, the second one lagged at least as long as the first was held
+        let held = entered.elapsed();
+        releases[first].send(()).unwrap();
+        let second = entered_recv.recv_timeout(timeout).unwrap();
+        assert_ne!(first, second);
+        releases[second].send(()).unwrap();
+        assert_eq!(done_recv.recv_timeout(timeout), Ok(first));
+        assert_eq!(done_recv.recv_timeout(timeout), Ok(second));
+
+        let metrics = exec.get_metrics();
+        assert_eq!(metrics.get_targets().len(), 2);
+        assert!(metrics.get_target("unknown").is_none());
+        let first_metrics = metrics.get_target(first).unwrap();
+        assert_eq!(first_metrics.get_check_duration().get_count(), 1);
+        assert!(*first_metrics.get_check_duration().get_sum() >= held);
+        let second_metrics = metrics.get_target(second).unwrap();
+        assert_eq!(second_metrics.get_scheduling_lag().get_count(), 1);
+        assert!(*second_metrics.get_scheduling_lag().get_sum() >= held);
+
+        // Checks triggered while paused are tracked as well
+        assert!(exec.pause("a"));
+        releases["a"].send(()).unwrap();
+        assert_eq!(block_on(exec.check_now("a")), Some(Status::Available));
+        assert_eq!(entered_recv.recv_timeout(timeout), Ok("a"));
+        assert_eq!(done_recv.recv_timeout(timeout), Ok("a"));
+        let metrics = exec.get_metrics();
+        let target = metrics.get_target("a").unwrap();
+        assert_eq!(target.get_check_duration().get_count(), 2);
+        assert_eq!(target.get_scheduling_lag().get_count(), 2);
+
+        exec.stop();
+        let metrics = exec.get_metrics();
+        assert_eq!(metrics.get_in_flight_checks(), 0);
+        assert_eq!(metrics.get_max_blocking_threads(), None);
+        assert_eq!(metrics.get_targets().len(), 2);
+    }
+
+    #[test]
+    fn duration_histogram() {
+        // Expectency: Observations are counted cumulatively in the bucket of their upper bound.
+        let mut histogram = DurationHistogram::default();
+        histogram.observe(Duration::from_millis(1));
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_millis(70));
+        histogram.observe(Duration::from_secs(60));
+
+        assert_eq!(histogram.get_count(), 4);
+        assert_eq!(histogram.get_sum(), &Duration::from_millis(60076));
+        let buckets = histogram.get_buckets();
+        assert_eq!(buckets.len(), HISTOGRAM_BUCKETS.len());
+        assert_eq!(buckets[0], (Duration::from_millis(5), 2));
+        assert_eq!(buckets[3], (Duration::from_millis(50), 2));
+        assert_eq!(buckets[4], (Duration::from_millis(100), 3));
+        assert_eq!(buckets[10], (Duration::from_secs(10), 3));
+    }
+
+    #[test]
+    fn async_target_maintenance_window() {
+        // Expectency: During maintenance, the handler is not called and observers are notified
+        //             about the expected downtime.
+        struct MaintenanceObserver(mpsc::Sender<bool>);
+        impl CheckObserver for MaintenanceObserver {
+            fn on_check(&self, report: &CheckReport) {
+                let _ = self.0.send(report.maintenance);
+            }
+        }
+
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().returning(|| Ok(Status::NotAvailable));
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {
+            panic!("Handler called during maintenance");
+        };
+        let window = MaintenanceWindow::Once {
+            start: SystemTime::now(),
+            end: SystemTime::now() + Duration::from_secs(60),
+        };
+        let target = AsyncTarget::from((mock, handler, Duration::from_millis(10))).add_maintenance_window(window);
+        assert!(target.in_maintenance(SystemTime::now()));
+        assert!(!target.in_maintenance(SystemTime::now() + Duration::from_secs(60)));
+
+        let (send, recv) = mpsc::channel();
+        let mut exec = AsyncTargetExecutor::new();
+        exec.add_observer(MaintenanceObserver(send));
+        exec.start(vec![target]).unwrap();
+        assert!(recv.recv_timeout(Duration::from_secs(1)).unwrap());
+        assert!(recv.recv_timeout(Duration::from_secs(1)).unwrap());
+        exec.stop();
+    }
+
+    #[test]
+    fn async_target_schedule() {
+        // Expectency: Scheduled targets are checked at the points in time of the schedule,
+        //             instead of after the check interval.
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        mock.expect_check_availability().returning(|| Ok(Status::Available));
+        let (send, recv) = mpsc::channel();
+        let handler = move |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {
+            let _ = send.send(SystemTime::now());
+        };
+        let schedule = CheckSchedule::Aligned {
+            interval: Duration::from_millis(200),
+            offset: Duration::from_millis(50),
+        };
+        let target = AsyncTarget::from((mock, handler, Duration::from_millis(10))).set_schedule(schedule.clone());
+        assert_eq!(target.get_schedule(), Some(&schedule));
+
+        let mut exec = AsyncTargetExecutor::new();
+        exec.start(vec![target]).unwrap();
+        for _ in 0..3 {
+            let time = recv.recv_timeout(Duration::from_secs(1)).unwrap();
+            let millis = time.duration_since(std::time::UNIX_EPOCH).unwrap().as_millis();
+            assert!(
+                (50..150).contains(&(millis % 200)),
+                "Check at unexpected time: {}",
+                millis
+            );
+        }
+        exec.stop();
+    }
+
+    #[test]
+    fn async_target_executor_dependencies() {
+        // Expectency: Invalid dependencies are rejected on start. Failed checks of a child, whose
+        //             parent is neither available nor degraded, are reported as unreachable due
+        //             to the parent, to observers and to the handler.
+        struct ParentObserver(mpsc::Sender<Option<String>>);
+        impl CheckObserver for ParentObserver {
+            fn on_check(&self, report: &CheckReport) {
+                let _ = self.0.send(report.unreachable_parent.map(String::from));
+            }
+        }
+
+        let build = |id: &'static str, parent: &str| {
+            let mut mock = MockTarget::new();
+            mock.expect_get_id().returning(move || String::from(id));
+            let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+            AsyncTarget::from((mock, handler, Duration::from_secs(1))).add_parent(parent)
+        };
+
+        let mut exec = AsyncTargetExecutor::new();
+        assert_eq!(
+            exec.start(vec![build("child", "missing")]).unwrap_err(),
+            DependencyError::UnknownParent(String::from("child"), String::from("missing"))
+        );
+        assert_eq!(
+            exec.start(vec![build("child", "other"), build("child", "other")])
+                .unwrap_err(),
+            DependencyError::DuplicateId(String::from("child"))
+        );
+        assert_eq!(
+            exec.start(vec![build("child", "other"), build("other", "child")])
+                .unwrap_err(),
+            DependencyError::Cycle(vec![
+                String::from("child"),
+                String::from("other"),
+                String::from("child")
+            ])
+        );
+
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("child"));
+        let (handler_send, handler_recv) = mpsc::channel();
+        let handler = move |_: &dyn Target, _: Status, _: OldStatus, error: Option<CheckTargetError>| {
+            let _ = handler_send.send(error.map(|error| error.to_string()));
+        };
+        let target = AsyncTarget::from((mock, handler, Duration::from_secs(1))).add_parent("parent");
+
+        let (send, recv) = mpsc::channel();
+        let observers: RwLock<Vec<SharedObserver>> = RwLock::new(vec![Arc::new(ParentObserver(send))]);
+        let statuses = Statuses::default();
+        let target = Mutex::new(target);
+        let check = |parent: Option<Status>| {
+            if let Some(parent) = parent {
+                statuses.write().unwrap().insert(String::from("parent"), parent);
+            }
+            let error = Some(CheckTargetError::from("Error"));
+            process_check(
+                &target,
+                "child",
+                &observers,
+                &statuses,
+                Status::NotAvailable,
+                error,
+                Duration::ZERO,
+            );
+            (recv.try_recv().unwrap(), handler_recv.try_recv().unwrap())
+        };
+        let unreachable = (
+            Some(String::from("parent")),
+            Some(String::from("Unreachable due to parent 'parent'")),
+        );
+        let failed = (None, Some(String::from("Error")));
+
+        // Parent not checked yet, available or degraded: The child failed on its own
+        assert_eq!(check(None), failed);
+        assert_eq!(check(Some(Status::Available)), failed);
+        assert_eq!(check(Some(Status::Degraded(String::from("slow")))), failed);
+
+        // Parent not available or unknown: The child is unreachable due to the parent
+        assert_eq!(check(Some(Status::NotAvailable)), unreachable);
+        assert_eq!(check(Some(Status::Unknown)), unreachable);
+    }
+
+    #[test]
+    fn async_target_flapping() {
+        // Expectency: A target changing its status too often within the window is flapping.
+        //             While flapping, the handler is not called. Once flapping stopped, the
+        //             handler receives the status before flapping as old status.
+        struct FlappingObserver(mpsc::Sender<bool>);
+        impl CheckObserver for FlappingObserver {
+            fn on_check(&self, _: &CheckReport) {}
+            fn on_flapping(&self, report: &CheckReport) {
+                let _ = self.0.send(report.flapping);
+            }
+        }
+
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(|| String::from("mock"));
+        let (handler_send, handler_recv) = mpsc::channel();
+        let handler = move |_: &dyn Target, status: Status, old_status: OldStatus, _: Option<CheckTargetError>| {
+            let _ = handler_send.send((status, old_status));
+        };
+        let window = Duration::from_secs(60);
+        let policy = FlappingPolicy::new(window, 3, 1);
+        let target = AsyncTarget::from((mock, handler, Duration::from_secs(1))).set_flapping_policy(policy.clone());
+        assert_eq!(target.get_flapping_policy(), Some(&policy));
+
+        let (send, recv) = mpsc::channel();
+        let observers: RwLock<Vec<SharedObserver>> = RwLock::new(vec![Arc::new(FlappingObserver(send))]);
+        let statuses = Statuses::default();
+        let target = Mutex::new(target);
+        let check = |status: Status| {
+            process_check(&target, "mock", &observers, &statuses, status, None, Duration::ZERO);
+        };
+
+        // Third status change starts flapping, the handler is not called anymore
+        check(Status::Available);
+        check(Status::NotAvailable);
+        check(Status::Available);
+        assert_eq!(handler_recv.try_iter().count(), 2);
+        assert_eq!(recv.try_recv(), Ok(true));
+        check(Status::NotAvailable);
+        assert!(target.lock().unwrap().is_flapping());
+        assert!(handler_recv.try_recv().is_err());
+
+        // After the window passed, flapping stops. Age the status changes instead of waiting.
+        for (time, _) in target.lock().unwrap().transitions.iter_mut() {
+            *time = time.checked_sub(window + Duration::from_secs(1)).unwrap();
+        }
+        check(Status::NotAvailable);
+        assert_eq!(recv.try_recv(), Ok(false));
+        assert!(!target.lock().unwrap().is_flapping());
+        assert_eq!(handler_recv.try_recv(), Ok((Status::NotAvailable, Status::Unknown)));
+    }
+
+    #[test]
+    fn async_target_panic_isolation() {
+        // Expectency: Panics of the target and the handler are caught and reported, the target
+        //             keeps being checked.
+        struct PanicObserver(mpsc::Sender<String>);
+        impl CheckObserver for PanicObserver {
+            fn on_check(&self, report: &CheckReport) {
+                let _ = self.0.send(format!("{}", report.status));
+                if let Some(error) = report.error {
+                    let _ = self.0.send(format!("{}", error));
+                }
+            }
+            fn on_handler_panic(&self, id: &str, message: &str) {
+                let _ = self.0.send(format!("{}: {}", id, message));
+            }
+        }
+
+        // Note: Mocks can not be used, a panicking expectation poisons the mock.
+        struct PanicTarget(AtomicUsize);
+        impl Target for PanicTarget {
+            fn get_id(&self) -> String {
+                String::from("mock")
+            }
+            fn check_availability(&self) -> Result<Status, CheckTargetError> {
+                match self.0.fetch_add(1, Ordering::SeqCst) {
+                    0 => panic!("Target failure"),
+                    _ => Ok(Status::Available),
+                }
+            }
+        }
+
+        let handler = |_: &dyn Target, status: Status, _: OldStatus, _: Option<CheckTargetError>| {
+            if status == Status::Available {
+                panic!("Handler failure");
+            }
+        };
+
+        let (send, recv) = mpsc::channel();
+        let mut exec = AsyncTargetExecutor::new();
+        exec.add_observer(PanicObserver(send));
+        exec.start(vec![AsyncTarget::from((
+            PanicTarget(AtomicUsize::new(0)),
+            handler,
+            Duration::from_millis(10),
+        ))])
+        .unwrap();
+        let mut events = recv.iter().take(6);
+        assert_eq!(events.next().unwrap(), "unknown");
+        assert_eq!(events.next().unwrap(), "Check panicked: Target failure");
+        assert_eq!(events.next().unwrap(), "available");
+        assert_eq!(events.next().unwrap(), "mock: Handler failure");
+        assert_eq!(events.next().unwrap(), "available");
+        assert_eq!(events.next().unwrap(), "mock: Handler failure");
+        exec.stop();
+    }
+
+    #[test]
+    fn async_target_hysteresis() {
+        // Expectency: A status change is confirmed after the configured number of consecutive
+        //             identical results. Interrupted sequences restart counting.
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+        let mut target = AsyncTarget::from((MockTarget::new(), handler, Duration::from_secs(1))).set_hysteresis(2);
+
+        let expected = vec![
+            (Status::Available, Status::Unknown, Status::Unknown),
+            (Status::Available, Status::Available, Status::Unknown),
+            (Status::NotAvailable, Status::Available, Status::Available),
+            (Status::Available, Status::Available, Status::Available),
+            (Status::NotAvailable, Status::Available, Status::Available),
+            (Status::NotAvailable, Status::NotAvailable, Status::Available),
+        ];
+        for (result, status, old_status) in expected {
+            assert_eq!(target.update_status(result), (status, old_status));
+        }
+    }
+
+    #[test]
+    fn async_target_hysteresis_degraded_reason() {
+        // Expectency: Changing the reason of a degradation is no status change. The new reason is
+        //             reported immediately, although hysteresis is configured.
+        let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+        let mut target = AsyncTarget::from((MockTarget::new(), handler, Duration::from_secs(1))).set_hysteresis(2);
+        let slow = Status::Degraded(String::from("slow"));
+        let lossy = Status::Degraded(String::from("lossy"));
+
+        target.update_status(slow.clone());
+        assert_eq!(target.update_status(slow.clone()), (slow.clone(), Status::Unknown));
+        assert_eq!(target.update_status(lossy.clone()), (lossy.clone(), slow));
+        assert_eq!(target.update_status(Status::Available), (lossy.clone(), lossy));
+    }
+
+    #[test]
+    fn async_target_executor_reload() {
+        // Expectency: On reload, unchanged targets keep running with their state, new targets
+        //             are started and removed targets are stopped.
+        let (send, recv) = mpsc::channel();
+        let build = |id: &'static str, fingerprint: &str, name: &'static str| {
+            let mut mock = MockTarget::new();
+            mock.expect_get_id().returning(move || String::from(id));
+            mock.expect_check_availability().returning(|| Ok(Status::Available));
+
+            let send = send.clone();
+            let handler = move |_: &dyn Target, _: Status, old: OldStatus, _: Option<CheckTargetError>| {
+                let _ = send.send((name, old));
+            };
+            AsyncTarget::from((mock, handler, Duration::from_millis(50))).set_fingerprint(String::from(fingerprint))
+        };
+
+        let mut exec = AsyncTargetExecutor::new();
+        exec.start(vec![build("a", "1", "a-old"), build("b", "1", "b")])
+            .unwrap();
+        sleep(Duration::from_millis(200));
+
+        exec.reload(vec![build("a", "1", "a-new"), build("c", "1", "c")])
+            .unwrap();
+        sleep(Duration::from_millis(100));
+        while recv.try_recv().is_ok() {}
+
+        sleep(Duration::from_millis(300));
+        assert_eq!(
+            exec.reload(vec![build("a", "1", "a-dup"), build("a", "2", "a-dup")])
+                .unwrap_err(),
+            DependencyError::DuplicateId(String::from("a"))
+        );
+        exec.stop();
+        let results: Vec<(&str, Status)> = recv.try_iter().collect();
+        assert!(results.contains(&("a-old", Status::Available)));
+        assert!(results.contains(&("c", Status::Available)));
+        assert!(results.iter().all(|(name, _)| *name == "a-old" || *name == "c"));
+    }
 }
@@ -0,0 +1,329 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Command line argument parsing of the reachable binary.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use reachable::nagios::NagiosState;
+use reachable::{IcmpTarget, ResolvePolicy, Target, TcpTarget};
+
+/// Usage text, printed on "--help" and on invalid arguments.
+pub const USAGE: &str = "\
+Usage: reachable [OPTIONS] <TARGET>...
+       reachable --nagios [--warning <TIME>] [--critical <TIME>] [OPTIONS] <TARGET>
+       reachable watch [OPTIONS] [--config <FILE>] [<TARGET>...]
+
+Check if all given targets are reachable. Exits with 0 if all targets are available
+(or degraded), with 1 if any target is not available and with 2 on invalid arguments.
+
+In Nagios mode, a single target is checked and reported as Nagios/Icinga plugin
+with exit code 0 (OK), 1 (WARNING), 2 (CRITICAL) or 3 (UNKNOWN).
+
+In watch mode, the targets are checked periodically and each status transition is
+printed as a line, until Ctrl-C is pressed. On unix, SIGHUP reloads the targets.
+
+Targets:
+  icmp://<host>          Check via ICMP echo request
+  tcp://<host>:<port>    Check via TCP connect
+  <host>:<port>          Same as tcp://<host>:<port>
+  <host>                 Same as icmp://<host>
+
+Options:
+  -4                     Resolve hosts to IPv4 addresses only
+  -6                     Resolve hosts to IPv6 addresses only
+  -t, --timeout <TIME>   Timeout of each check, e.g. 500ms or 2s
+  -j, --json             Print results as JSON instead of a table, in watch mode
+                         print transitions as JSON lines
+  -i, --interval <TIME>  Watch mode: Time between two checks of a target (default: 1s)
+  -c, --config <FILE>    Watch mode: Load additional targets from a configuration file
+  --nagios               Report as Nagios/Icinga plugin
+  --warning <TIME>       Nagios mode: Latency, above which the state is WARNING
+  --critical <TIME>      Nagios mode: Latency, above which the state is CRITICAL
+  -h, --help             Print this help
+";
+
+/// Default check interval of the watch mode.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Operation mode of the binary.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Check all targets once.
+    Check,
+    /// Check a single target once and report as Nagios plugin.
+    Nagios,
+    /// Check all targets periodically and print all transitions.
+    Watch,
+}
+
+impl Command {
+    /// Get the exit code reporting invalid arguments or targets. Plugins report them as UNKNOWN.
+    pub fn usage_exit_code(&self) -> u8 {
+        match self {
+            Command::Nagios => NagiosState::Unknown.exit_code() as u8,
+            Command::Check | Command::Watch => 2,
+        }
+    }
+}
+
+/// Target specification as given on the command line and the [Target] constructed from it.
+pub type NamedTarget = (String, Box<dyn Target + Send>);
+
+/// Format of the printed results.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+/// Parsed command line arguments.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub warning: Option<Duration>,
+    pub critical: Option<Duration>,
+    pub interval: Duration,
+    pub config: Option<String>,
+    pub resolve_policy: ResolvePolicy,
+    pub timeout: Option<Duration>,
+    pub format: Format,
+    pub help: bool,
+    pub targets: Vec<String>,
+}
+
+impl Args {
+    /// Parse given command line arguments, excluding the program name.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+        let mut parsed = Args {
+            command: Command::Check,
+            warning: None,
+            critical: None,
+            interval: DEFAULT_INTERVAL,
+            config: None,
+            resolve_policy: ResolvePolicy::Agnostic,
+            timeout: None,
+            format: Format::Table,
+            help: false,
+            targets: Vec::new(),
+        };
+
+        let mut args = args.into_iter().peekable();
+        if args.peek().map(String::as_str) == Some("watch") {
+            parsed.command = Command::Watch;
+            args.next();
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-4" => parsed.resolve_policy = ResolvePolicy::ResolveToIPv4,
+                "-6" => parsed.resolve_policy = ResolvePolicy::ResolveToIPv6,
+                "-j" | "--json" => parsed.format = Format::Json,
+                "-h" | "--help" => parsed.help = true,
+                "-t" | "--timeout" => {
+                    let value = args.next().ok_or_else(|| format!("Missing value of '{}'", arg))?;
+                    parsed.timeout = Some(parse_duration(&value)?);
+                }
+                "-i" | "--interval" => {
+                    let value = args.next().ok_or_else(|| format!("Missing value of '{}'", arg))?;
+                    parsed.interval = match parse_duration(&value)? {
+                        interval if interval.is_zero() => return Err(String::from("Interval must not be zero")),
+                        interval => interval,
+                    };
+                }
+                "--nagios" if parsed.command == Command::Check => parsed.command = Command::Nagios,
+                "--warning" | "--critical" => {
+                    let value = args.next().ok_or_else(|| format!("Missing value of '{}'", arg))?;
+                    match arg.as_str() {
+                        "--warning" => parsed.warning = Some(parse_duration(&value)?),
+                        _ => parsed.critical = Some(parse_duration(&value)?),
+                    }
+                }
+                "-c" | "--config" => {
+                    parsed.config = Some(args.next().ok_or_else(|| format!("Missing value of '{}'", arg))?);
+                }
+                option if option.starts_with('-') => return Err(format!("Unknown option '{}'", option)),
+                _ => parsed.targets.push(arg),
+            }
+        }
+
+        if parsed.command != Command::Nagios && (parsed.warning.is_some() || parsed.critical.is_some()) {
+            return Err(String::from("Options '--warning' and '--critical' require Nagios mode"));
+        }
+        if parsed.command == Command::Nagios && parsed.targets.len() > 1 {
+            return Err(String::from("Nagios mode requires exactly one target"));
+        }
+        if parsed.command != Command::Watch && parsed.config.is_some() {
+            return Err(String::from("Option '--config' requires watch mode"));
+        }
+        if parsed.targets.is_empty() && parsed.config.is_none() && !parsed.help {
+            return Err(String::from("No targets given"));
+        }
+        Ok(parsed)
+    }
+
+    /// Construct all given targets.
+    pub fn build_targets(&self) -> Result<Vec<NamedTarget>, String> {
+        self.targets
+            .iter()
+            .map(|spec| Ok((spec.clone(), parse_target(spec, self.resolve_policy, self.timeout)?)))
+            .collect()
+    }
+}
+
+/// Parse a target specification like "tcp://localhost:80" into a [Target].
+pub fn parse_target(
+    spec: &str,
+    resolve_policy: ResolvePolicy,
+    timeout: Option<Duration>,
+) -> Result<Box<dyn Target + Send>, String> {
+    let error = |error| format!("Invalid target '{}': {}", spec, error);
+
+    let (kind, address) = match spec.split_once("://") {
+        Some((kind, address)) => (kind, address),
+        // Note: A colon in a bare IPv6 address is no port separator
+        None if spec.contains(':') && spec.parse::<std::net::Ipv6Addr>().is_err() => ("tcp", spec),
+        None => ("icmp", spec),
+    };
+
+    match kind {
+        "icmp" => {
+            let mut target = IcmpTarget::from_str(address)
+                .map_err(error)?
+                .set_resolve_policy(resolve_policy);
+            if let Some(timeout) = timeout {
+                target = target.set_echo_timeout(timeout);
+            }
+            Ok(Box::new(target))
+        }
+        "tcp" => {
+            let mut target = TcpTarget::from_str(address)
+                .map_err(error)?
+                .set_resolve_policy(resolve_policy);
+            if let Some(timeout) = timeout {
+                target = target.set_connect_timeout(timeout);
+            }
+            Ok(Box::new(target))
+        }
+        kind => Err(format!("Invalid target '{}': Unknown kind '{}'", spec, kind)),
+    }
+}
+
+/// Parse a [Duration] from a number followed by a unit: "ms", "s", "m" or "h".
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    reachable::config::parse_duration(s).map_err(|error| format!("Invalid duration '{}': {}", s, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| String::from(*arg)))
+    }
+
+    #[test]
+    fn args_parse() {
+        let parsed = args(&["-6", "--json", "-t", "2s", "localhost", "tcp://localhost:80"]).unwrap();
+        assert_eq!(parsed.resolve_policy, ResolvePolicy::ResolveToIPv6);
+        assert_eq!(parsed.format, Format::Json);
+        assert_eq!(parsed.timeout, Some(Duration::from_secs(2)));
+        assert_eq!(parsed.targets, vec!["localhost", "tcp://localhost:80"]);
+        assert!(!parsed.help);
+
+        assert!(args(&["--help"]).unwrap().help);
+        assert_eq!(args(&[]).unwrap_err(), "No targets given");
+        assert_eq!(args(&["-x", "localhost"]).unwrap_err(), "Unknown option '-x'");
+        assert_eq!(args(&["localhost", "-t"]).unwrap_err(), "Missing value of '-t'");
+        assert!(args(&["-t", "2", "localhost"]).is_err());
+    }
+
+    #[test]
+    fn args_parse_watch() {
+        let parsed = args(&["watch", "-i", "10s", "--config", "monitoring.toml"]).unwrap();
+        assert_eq!(parsed.command, Command::Watch);
+        assert_eq!(parsed.interval, Duration::from_secs(10));
+        assert_eq!(parsed.config, Some(String::from("monitoring.toml")));
+        assert!(parsed.targets.is_empty());
+
+        assert_eq!(args(&["localhost"]).unwrap().interval, DEFAULT_INTERVAL);
+        assert_eq!(args(&["watch"]).unwrap_err(), "No targets given");
+        assert_eq!(
+            args(&["watch", "-i", "0s", "localhost"]).unwrap_err(),
+            "Interval must not be zero"
+        );
+        assert_eq!(
+            args(&["-c", "monitoring.toml"]).unwrap_err(),
+            "Option '--config' requires watch mode"
+        );
+    }
+
+    #[test]
+    fn args_parse_nagios() {
+        let parsed = args(&["--nagios", "--warning", "100ms", "--critical", "1s", "localhost:80"]).unwrap();
+        assert_eq!(parsed.command, Command::Nagios);
+        assert_eq!(parsed.warning, Some(Duration::from_millis(100)));
+        assert_eq!(parsed.critical, Some(Duration::from_secs(1)));
+
+        assert_eq!(
+            args(&["--nagios", "a:80", "b:80"]).unwrap_err(),
+            "Nagios mode requires exactly one target"
+        );
+        assert_eq!(
+            args(&["--warning", "1s", "a:80"]).unwrap_err(),
+            "Options '--warning' and '--critical' require Nagios mode"
+        );
+        assert_eq!(
+            args(&["watch", "--nagios", "a:80"]).unwrap_err(),
+            "Unknown option '--nagios'"
+        );
+    }
+
+    #[test]
+    fn args_usage_exit_code() {
+        // Expectency: Invalid targets are reported as UNKNOWN in Nagios mode, with 2 otherwise.
+        let parsed = args(&["--nagios", "tcp://localhost"]).unwrap();
+        assert!(parsed.build_targets().is_err());
+        assert_eq!(parsed.command.usage_exit_code(), 3);
+
+        let parsed = args(&["tcp://localhost"]).unwrap();
+        assert!(parsed.build_targets().is_err());
+        assert_eq!(parsed.command.usage_exit_code(), 2);
+        assert_eq!(Command::Watch.usage_exit_code(), 2);
+    }
+
+    #[test]
+    fn args_parse_target() {
+        let parse = |spec| parse_target(spec, ResolvePolicy::Agnostic, None).map(|target| target.get_id());
+        assert_eq!(parse("icmp://localhost"), Ok(String::from("localhost")));
+        assert_eq!(parse("localhost"), Ok(String::from("localhost")));
+        assert_eq!(parse("::1"), Ok(String::from("::1")));
+        assert_eq!(parse("tcp://localhost:80"), Ok(String::from("localhost:80")));
+        assert_eq!(parse("localhost:80"), Ok(String::from("localhost:80")));
+
+        assert!(parse("tcp://localhost").is_err());
+        assert!(parse("icmp://").is_err());
+        assert_eq!(
+            parse("udp://localhost:53"),
+            Err(String::from("Invalid target 'udp://localhost:53': Unknown kind 'udp'"))
+        );
+    }
+
+    #[test]
+    fn args_parse_duration() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_duration("2").is_err());
+        assert!(parse_duration("s").is_err());
+        assert_eq!(
+            parse_duration("99999999999999999h"),
+            Err(String::from(
+                "Invalid duration '99999999999999999h': Duration is too large"
+            ))
+        );
+    }
+}
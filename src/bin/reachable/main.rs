@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Command line tool checking the reachability of targets, e.g. from shell scripts.
+//!
+//! Run "reachable --help" for usage information.
+
+mod args;
+mod output;
+mod watch;
+
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+use reachable::nagios::NagiosCheck;
+use reachable::{Status, Target};
+
+use args::{Args, Command, Format, NamedTarget, USAGE};
+use output::CheckResult;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) if args.help => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("Error: {}\n\n{}", error, USAGE);
+
+            // Note: Plugins report invalid arguments as UNKNOWN
+            let command = match std::env::args().any(|arg| arg == "--nagios") {
+                true => Command::Nagios,
+                false => Command::Check,
+            };
+            return ExitCode::from(command.usage_exit_code());
+        }
+    };
+
+    if args.command == Command::Watch {
+        return watch::run(args);
+    }
+
+    let targets = match args.build_targets() {
+        Ok(targets) => targets,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            return ExitCode::from(args.command.usage_exit_code());
+        }
+    };
+
+    if args.command == Command::Nagios {
+        return check_nagios(&args, targets);
+    }
+
+    let results = check_all(targets);
+    match args.format {
+        Format::Table => print!("{}", output::render_table(&results)),
+        Format::Json => print!("{}", output::render_json(&results)),
+    }
+
+    if results.iter().all(CheckResult::is_reachable) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+/// Check the single given target and report as Nagios plugin.
+fn check_nagios(args: &Args, targets: Vec<NamedTarget>) -> ExitCode {
+    let mut check = NagiosCheck::new();
+    if let Some(warning) = args.warning {
+        check = check.set_warning(warning);
+    }
+    if let Some(critical) = args.critical {
+        check = check.set_critical(critical);
+    }
+
+    // Note: Argument parsing ensures exactly one target in Nagios mode
+    let result = check.check(targets[0].1.as_ref());
+    println!("{}", result);
+    ExitCode::from(result.get_state().exit_code() as u8)
+}
+
+/// Check all given targets in parallel. The results keep the order of the given targets.
+fn check_all(targets: Vec<NamedTarget>) -> Vec<CheckResult> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .into_iter()
+            .map(|(spec, target)| scope.spawn(move || check(spec, target.as_ref())))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+/// Check a single target.
+fn check(spec: String, target: &dyn Target) -> CheckResult {
+    let started = Instant::now();
+    let (status, error) = match target.check_availability() {
+        Ok(status) => (status, None),
+        Err(error) => (Status::Unknown, Some(error.to_string())),
+    };
+
+    CheckResult {
+        spec,
+        status,
+        error,
+        duration: started.elapsed(),
+    }
+}
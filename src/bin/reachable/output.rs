@@ -0,0 +1,226 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Rendering of check results of the reachable binary.
+
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+use reachable::schedule::format_utc_time;
+use reachable::{CheckReport, Status};
+
+use serde::Serialize;
+
+/// Result of a single check.
+#[derive(Debug)]
+pub struct CheckResult {
+    /// Target specification as given on the command line.
+    pub spec: String,
+    pub status: Status,
+    pub error: Option<String>,
+    pub duration: Duration,
+}
+
+impl CheckResult {
+    /// Check if the target is reachable, meaning available or degraded.
+    pub fn is_reachable(&self) -> bool {
+        matches!(self.status, Status::Available | Status::Degraded(_))
+    }
+}
+
+/// JSON representation of a [CheckResult].
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    target: &'a str,
+    status: &'static str,
+    reason: Option<&'a str>,
+    duration_ms: u64,
+    error: Option<&'a str>,
+}
+
+/// JSON representation of a status transition.
+#[derive(Serialize)]
+struct JsonTransition<'a> {
+    timestamp: u64,
+    target: &'a str,
+    status: &'static str,
+    reason: Option<&'a str>,
+    old_status: &'static str,
+    error: Option<String>,
+}
+
+/// Render given results as table with one line per target.
+pub fn render_table(results: &[CheckResult]) -> String {
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| {
+            [
+                result.spec.clone(),
+                result.status.to_string(),
+                format!("{}ms", result.duration.as_millis()),
+                result.error.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    let header = [
+        String::from("TARGET"),
+        String::from("STATUS"),
+        String::from("TIME"),
+        String::from("ERROR"),
+    ];
+    let mut widths = header.clone().map(|column| column.len());
+    for row in rows.iter() {
+        for (width, column) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(column.len());
+        }
+    }
+
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = format!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
+/// Render given results as JSON array.
+pub fn render_json(results: &[CheckResult]) -> String {
+    let entries: Vec<JsonResult> = results
+        .iter()
+        .map(|result| {
+            let (status, reason) = status_value(&result.status);
+            JsonResult {
+                target: &result.spec,
+                status,
+                reason,
+                duration_ms: result.duration.as_millis() as u64,
+                error: result.error.as_deref(),
+            }
+        })
+        .collect();
+    format!("{}\n", serde_json::to_string(&entries).unwrap_or_default())
+}
+
+/// Render the status transition of given report as human readable line.
+pub fn render_transition(report: &CheckReport) -> String {
+    let mut line = format!(
+        "{} {}: {} -> {}",
+        format_utc_time(report.timestamp),
+        report.id,
+        report.old_status,
+        report.status
+    );
+    if let Some(error) = report.error {
+        let _ = write!(line, ", Error: {}", error);
+    }
+    line
+}
+
+/// Render the status transition of given report as JSON line.
+pub fn render_transition_json(report: &CheckReport) -> String {
+    let (status, reason) = status_value(report.status);
+    let transition = JsonTransition {
+        timestamp: report
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        target: report.id,
+        status,
+        reason,
+        old_status: status_value(report.old_status).0,
+        error: report.error.map(|error| error.to_string()),
+    };
+    serde_json::to_string(&transition).unwrap_or_default()
+}
+
+/// Get the machine readable value of given status and the reason of a degradation.
+fn status_value(status: &Status) -> (&'static str, Option<&str>) {
+    match status {
+        Status::Unknown => ("unknown", None),
+        Status::Available => ("available", None),
+        Status::Degraded(reason) => ("degraded", Some(reason)),
+        Status::NotAvailable => ("not_available", None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<CheckResult> {
+        vec![
+            CheckResult {
+                spec: String::from("localhost"),
+                status: Status::Available,
+                error: None,
+                duration: Duration::from_millis(3),
+            },
+            CheckResult {
+                spec: String::from("tcp://localhost:1"),
+                status: Status::Unknown,
+                error: Some(String::from("Failed \"badly\"")),
+                duration: Duration::from_millis(120),
+            },
+        ]
+    }
+
+    #[test]
+    fn output_render_table() {
+        let expected = concat!(
+            "TARGET             STATUS      TIME  ERROR\n",
+            "localhost          available    3ms\n",
+            "tcp://localhost:1  unknown    120ms  Failed \"badly\"\n",
+        );
+        assert_eq!(render_table(&results()), expected);
+    }
+
+    #[test]
+    fn output_render_json() {
+        let expected = concat!(
+            "[{\"target\":\"localhost\",\"status\":\"available\",\"reason\":null,\"duration_ms\":3,\"error\":null},",
+            "{\"target\":\"tcp://localhost:1\",\"status\":\"unknown\",\"reason\":null,\"duration_ms\":120,",
+            "\"error\":\"Failed \\\"badly\\\"\"}]\n"
+        );
+        assert_eq!(render_json(&results()), expected);
+    }
+
+    #[test]
+    fn output_render_transition() {
+        let error = reachable::CheckTargetError::from("Error");
+        let old_status = Status::Degraded(String::from("slow"));
+        let report = CheckReport::new(
+            "localhost:80",
+            &Status::Unknown,
+            &old_status,
+            Some(&error),
+            Duration::from_millis(10),
+            UNIX_EPOCH + Duration::from_secs(1633262400),
+        );
+
+        assert_eq!(
+            render_transition(&report),
+            "2021-10-03 12:00:00 localhost:80: degraded (slow) -> unknown, Error: Error"
+        );
+        assert_eq!(
+            render_transition_json(&report),
+            concat!(
+                "{\"timestamp\":1633262400,\"target\":\"localhost:80\",\"status\":\"unknown\",\"reason\":null,",
+                "\"old_status\":\"degraded\",\"error\":\"Error\"}"
+            )
+        );
+    }
+}
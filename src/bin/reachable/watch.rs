@@ -0,0 +1,173 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Watch mode of the reachable binary: Check targets periodically until Ctrl-C is pressed. On unix,
+//! SIGHUP reloads the configuration file and the targets given on the command line.
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use reachable::{
+    AsyncTarget, AsyncTargetExecutor, CheckObserver, CheckReport, CheckTargetError, OldStatus, Status, Target,
+};
+
+use crate::args::{Args, Format};
+use crate::output;
+
+/// Observer printing each status transition as a line to stdout.
+struct TransitionPrinter {
+    format: Format,
+}
+
+impl CheckObserver for TransitionPrinter {
+    fn on_check(&self, report: &CheckReport) {
+        if report.status.same_state(report.old_status) {
+            return;
+        }
+
+        let line = match self.format {
+            Format::Table => output::render_transition(report),
+            Format::Json => output::render_transition_json(report),
+        };
+
+        // Note: Flush each line to allow streaming into pipes.
+        let mut stdout = io::stdout().lock();
+        let _ = writeln!(stdout, "{}", line);
+        let _ = stdout.flush();
+    }
+}
+
+/// Run the watch mode with given arguments.
+pub fn run(args: Args) -> ExitCode {
+    let targets = match load_targets(&args) {
+        Ok(targets) => targets,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut exec = AsyncTargetExecutor::new();
+    exec.add_observer(TransitionPrinter { format: args.format });
+    if let Err(error) = exec.start(targets) {
+        eprintln!("Error: {}", error);
+        return ExitCode::from(2);
+    }
+
+    // Run until Ctrl-C is pressed, reload the targets on SIGHUP
+    let result = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .and_then(|runtime| runtime.block_on(wait(&args, &mut exec)));
+    exec.stop();
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: Failed to wait for Ctrl-C: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Wait for Ctrl-C. Each SIGHUP reloads the targets into the running executor.
+#[cfg(unix)]
+async fn wait(args: &Args, exec: &mut AsyncTargetExecutor) -> io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    loop {
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => return result,
+            _ = hangup.recv() => reload(args, exec),
+        }
+    }
+}
+
+/// Wait for Ctrl-C. Reloading on SIGHUP is only supported on unix.
+#[cfg(not(unix))]
+async fn wait(_: &Args, _: &mut AsyncTargetExecutor) -> io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+/// Reload the targets into the running executor. On errors, the previous targets keep running.
+#[cfg(unix)]
+fn reload(args: &Args, exec: &mut AsyncTargetExecutor) {
+    let result = load_targets(args).and_then(|targets| exec.reload(targets).map_err(|error| error.to_string()));
+    match result {
+        Ok(()) => eprintln!("Reloaded targets"),
+        Err(error) => eprintln!("Error: Failed to reload targets: {}", error),
+    }
+}
+
+/// Load the targets of the given configuration file and the command line.
+fn load_targets(args: &Args) -> Result<Vec<AsyncTarget<'static>>, String> {
+    let mut targets = match &args.config {
+        Some(path) => reachable::config::load(path).map_err(|error| error.to_string())?,
+        None => Vec::new(),
+    };
+
+    // Note: The fingerprint covers all settings of a target given on the command line. A reload
+    // keeps unchanged targets running with their status.
+    let handler = |_: &dyn Target, _: Status, _: OldStatus, _: Option<CheckTargetError>| {};
+    for (spec, target) in args.build_targets()?.into_iter() {
+        let fingerprint = format!("{:?}", (&spec, args.interval, args.timeout, args.resolve_policy));
+        targets.push(AsyncTarget::new(target, Box::new(handler), args.interval).set_fingerprint(fingerprint));
+    }
+    Ok(targets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Sender};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Observer forwarding each status transition.
+    struct TransitionSender(Mutex<Sender<(Status, Status)>>);
+
+    impl CheckObserver for TransitionSender {
+        fn on_check(&self, report: &CheckReport) {
+            if !report.status.same_state(report.old_status) {
+                let _ = self
+                    .0
+                    .lock()
+                    .unwrap()
+                    .send((report.status.clone(), report.old_status.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn watch_reload_unchanged_targets() {
+        // Expectency: Reloading identical command line targets keeps them running with their
+        //             status, no transition is reported.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let id = listener.local_addr().unwrap().to_string();
+        let args = Args::parse([
+            String::from("watch"),
+            String::from("-i"),
+            String::from("1h"),
+            id.clone(),
+        ])
+        .unwrap();
+
+        let (send, recv) = mpsc::channel();
+        let mut exec = AsyncTargetExecutor::new();
+        exec.add_observer(TransitionSender(Mutex::new(send)));
+        exec.start(load_targets(&args).unwrap()).unwrap();
+        let transition = recv.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(transition, (Status::Available, Status::Unknown));
+
+        exec.reload(load_targets(&args).unwrap()).unwrap();
+        assert_eq!(block_on(exec.check_now(&id)), Some(Status::Available));
+        exec.stop();
+        assert!(recv.try_recv().is_err());
+    }
+}
@@ -0,0 +1,1367 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module containing a declarative monitoring configuration, loadable from TOML or YAML files.
+//!
+//! A configuration consists of four sections:
+//! * defaults: settings applied to all targets.
+//! * groups: named sets of settings. Targets join groups, inheriting their settings.
+//! * sinks: named notifications sinks, called with the check results of a target.
+//! * targets: the list of targets to check. Targets may declare "parents" by their address, e.g.
+//!   the gateway in front of them (see [AsyncTarget::add_parent]).
+//!
+//! Settings are resolved per target in the following order: target, groups (in order of
+//! declaration on the target), defaults. The setting "interval" is used while a target is
+//! reachable, "failing_interval" after a failed check. While a target stays not available, the
+//! failing interval is multiplied by "backoff" with each check, up to "max_interval" (see
+//! [IntervalPolicy]). Alternatively, the setting "schedule" performs checks at the matches of a
+//! cron expression in UTC, e.g. "*/5 * * * *" (see [AsyncTarget::set_schedule]). The setting
+//! "timeout" is used as connect timeout of TCP targets and as echo timeout of ICMP targets, while
+//! "check_timeout" is the deadline of a whole check enforced by the executor (see
+//! [AsyncTarget::set_check_timeout]). Likewise, "degraded_latency" is the connect
+//! latency (TCP) or average round trip time (ICMP) above which a target is reported as degraded.
+//! ICMP targets accept further echo request options in the table "icmp".
+//!
+//! The setting "maintenance" lists windows during which a target is expected to be unavailable:
+//! either one-off windows with "start" and "end" (UTC, e.g. "2021-10-03T22:00") or recurring
+//! windows with a "cron" expression and a "duration" (see [MaintenanceWindow]). In contrast to
+//! other settings, the maintenance windows of target, groups and defaults are combined.
+//!
+//! The table "flapping" enables flapping detection (see [FlappingPolicy]): a target is flapping
+//! after "start_threshold" status changes within "window", until at most "stop_threshold"
+//! changes are left.
+//!
+//! Sinks of type "command" run their command in the background, checks do not wait for it. A
+//! command still running after [COMMAND_SINK_TIMEOUT] is killed.
+//!
+//! # Example
+//! ```toml
+//! [defaults]
+//! interval = "10s"
+//! sinks = ["console"]
+//!
+//! [groups.databases]
+//! interval = "30s"
+//! failing_interval = "5s"
+//! backoff = 2.0
+//! max_interval = "10m"
+//! retries = 2
+//! hysteresis = 3
+//! check_timeout = "10s"
+//! maintenance = [{ cron = "0 1 * * *", duration = "2h" }]
+//! flapping = { window = "30m", start_threshold = 5, stop_threshold = 1 }
+//!
+//! [sinks.console]
+//! type = "stdout"
+//!
+//! [sinks.pager]
+//! type = "command"
+//! command = "/usr/local/bin/page-oncall"
+//! args = ["--team", "ops"]
+//!
+//! [[targets]]
+//! kind = "icmp"
+//! address = "gateway.example.com"
+//! timeout = "1s"
+//! icmp = { count = 5, echo_interval = "200ms", success_ratio = 0.6, max_rtt = "150ms", max_jitter = "30ms" }
+//!
+//! [[targets]]
+//! kind = "tcp"
+//! address = "db.example.com:5432"
+//! groups = ["databases"]
+//! parents = ["gateway.example.com"]
+//! sinks = ["console", "pager"]
+//! timeout = "2s"
+//! degraded_latency = "250ms"
+//! ```
+//!
+//! # Notes
+//! Requires crate to be configured with feature "config".
+
+// Imports
+use super::async_target::verify_dependencies;
+use super::schedule::{parse_utc_time, CheckSchedule, CronSchedule, MaintenanceWindow};
+use super::{
+    AsyncTarget, BoxedHandler, CheckTargetError, ConfigError, DependencyError, FlappingPolicy, IcmpTarget,
+    IntervalPolicy, ResolvePolicy, Status, TcpTarget,
+};
+use super::{BoxedTarget, Target};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default interval between two availability checks of a target.
+pub const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time a command of a sink may run, before it is killed.
+pub const COMMAND_SINK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Declarative monitoring configuration. See the module documentation for the file format.
+#[derive(Deserialize, PartialEq, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Settings applied to all targets.
+    #[serde(default)]
+    defaults: Settings,
+    /// Named settings, targets can join.
+    #[serde(default)]
+    groups: BTreeMap<String, Settings>,
+    /// Named notification sinks.
+    #[serde(default)]
+    sinks: BTreeMap<String, SinkConfig>,
+    /// All targets to check.
+    #[serde(default)]
+    targets: Vec<TargetConfig>,
+}
+
+/// Settings shared between defaults, groups and targets. All settings are optional.
+#[derive(Deserialize, PartialEq, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    interval: Option<String>,
+    schedule: Option<String>,
+    failing_interval: Option<String>,
+    backoff: Option<f64>,
+    max_interval: Option<String>,
+    retries: Option<u32>,
+    hysteresis: Option<u32>,
+    check_timeout: Option<String>,
+    timeout: Option<String>,
+    degraded_latency: Option<String>,
+    resolve_policy: Option<String>,
+    sinks: Option<Vec<String>>,
+    maintenance: Option<Vec<MaintenanceConfig>>,
+    flapping: Option<FlappingConfig>,
+}
+
+/// Maintenance window: either a one-off window given by "start" and "end" or a recurring window
+/// given by a cron expression and a duration.
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct MaintenanceConfig {
+    start: Option<String>,
+    end: Option<String>,
+    cron: Option<String>,
+    duration: Option<String>,
+}
+
+/// Flapping detection: a target is flapping after "start_threshold" status changes within
+/// "window", until at most "stop_threshold" changes are left.
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct FlappingConfig {
+    window: String,
+    start_threshold: usize,
+    stop_threshold: usize,
+}
+
+/// Supported kinds of targets.
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum TargetKind {
+    Icmp,
+    Tcp,
+}
+
+/// Configuration entry of a single target.
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct TargetConfig {
+    kind: TargetKind,
+    address: String,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    parents: Vec<String>,
+    interval: Option<String>,
+    schedule: Option<String>,
+    failing_interval: Option<String>,
+    backoff: Option<f64>,
+    max_interval: Option<String>,
+    retries: Option<u32>,
+    hysteresis: Option<u32>,
+    check_timeout: Option<String>,
+    timeout: Option<String>,
+    degraded_latency: Option<String>,
+    resolve_policy: Option<String>,
+    sinks: Option<Vec<String>>,
+    maintenance: Option<Vec<MaintenanceConfig>>,
+    flapping: Option<FlappingConfig>,
+    icmp: Option<IcmpOptions>,
+}
+
+/// Echo request options of ICMP targets.
+#[derive(Deserialize, PartialEq, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct IcmpOptions {
+    count: Option<u32>,
+    echo_interval: Option<String>,
+    success_ratio: Option<f64>,
+    max_rtt: Option<String>,
+    max_jitter: Option<String>,
+    degraded_jitter: Option<String>,
+    degraded_loss_ratio: Option<f64>,
+    ttl: Option<u8>,
+    payload_size: Option<u16>,
+    dscp: Option<u8>,
+}
+
+/// Configuration entry of a notification sink.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+enum SinkConfig {
+    /// Print check results to stdout.
+    Stdout {
+        #[serde(default)]
+        transitions_only: bool,
+    },
+    /// Execute a command with each check result. The result is handed over as environment
+    /// variables REACHABLE_TARGET, REACHABLE_STATUS, REACHABLE_OLD_STATUS and REACHABLE_ERROR.
+    /// The command runs in the background and is killed after [COMMAND_SINK_TIMEOUT].
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default = "default_transitions_only")]
+        transitions_only: bool,
+    },
+}
+
+fn default_transitions_only() -> bool {
+    true
+}
+
+impl Config {
+    /// Parse a [Config] from a string in TOML format.
+    ///
+    /// # Arguments
+    /// * content: string containing the configuration.
+    ///
+    /// # Returns
+    /// * On success, the parsed [Config]. The [Config] is not validated until
+    ///   [Config::build_async_targets] is called.
+    /// * On failure, a [ConfigError] describing the syntax error.
+    pub fn from_toml_str(content: &str) -> Result<Config, ConfigError> {
+        toml::from_str(content).map_err(|error| ConfigError::from(("Failed to parse TOML", boxed(error))))
+    }
+
+    /// Parse a [Config] from a string in YAML format.
+    ///
+    /// # Arguments
+    /// * content: string containing the configuration.
+    ///
+    /// # Returns
+    /// * On success, the parsed [Config]. The [Config] is not validated until
+    ///   [Config::build_async_targets] is called.
+    /// * On failure, a [ConfigError] describing the syntax error.
+    pub fn from_yaml_str(content: &str) -> Result<Config, ConfigError> {
+        serde_yaml::from_str(content).map_err(|error| ConfigError::from(("Failed to parse YAML", boxed(error))))
+    }
+
+    /// Read and parse a [Config] from a file. The file format is determined by its extension:
+    /// ".toml" for TOML, ".yaml" or ".yml" for YAML.
+    ///
+    /// # Arguments
+    /// * path: path of the configuration file.
+    ///
+    /// # Returns
+    /// * On success, the parsed [Config].
+    /// * On failure, a [ConfigError]. Either the file could not be read or its content is invalid.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let parse = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Config::from_toml_str,
+            Some("yaml") | Some("yml") => Config::from_yaml_str,
+            _ => {
+                return Err(ConfigError::from(
+                    "Unsupported file extension, expected toml, yaml or yml",
+                ))
+            }
+        };
+
+        let content = fs::read_to_string(path).map_err(|error| ConfigError::from(("Failed to read file", error)))?;
+        parse(&content)
+    }
+
+    /// Validate this [Config] and build all configured targets.
+    ///
+    /// # Returns
+    /// * On success, a vector of [AsyncTarget]s ready to be handed to
+    ///   [AsyncTargetExecutor::start](super::AsyncTargetExecutor::start).
+    /// * On failure, a [ConfigError::InvalidEntry] pointing at the first invalid entry.
+    ///
+    /// # Example
+    /// ```
+    /// # use reachable::config::Config;
+    /// let config = Config::from_toml_str(
+    ///     r#"
+    ///     [[targets]]
+    ///     kind = "tcp"
+    ///     address = "127.0.0.1:80"
+    ///     interval = "1s"
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// let targets = config.build_async_targets().unwrap();
+    /// assert_eq!(targets.len(), 1);
+    /// ```
+    pub fn build_async_targets(&self) -> Result<Vec<AsyncTarget<'static>>, ConfigError> {
+        self.validate_sections()?;
+
+        let mut ids = HashSet::new();
+        let mut targets = Vec::with_capacity(self.targets.len());
+        for (index, entry) in self.targets.iter().enumerate() {
+            let target = self.build_async_target(index, entry)?;
+            if !ids.insert(target.get_id()) {
+                return Err(invalid_entry(format!("targets[{}].address", index), "Duplicate target"));
+            }
+            targets.push(target);
+        }
+
+        verify_dependencies(&targets).map_err(|error| {
+            let id = match &error {
+                DependencyError::UnknownParent(id, _) | DependencyError::DuplicateId(id) => id,
+                DependencyError::Cycle(ids) => &ids[0],
+            };
+            let index = targets
+                .iter()
+                .position(|target| &target.get_id() == id)
+                .unwrap_or_default();
+            entry_error(format!("targets[{}].parents", index), error)
+        })?;
+        Ok(targets)
+    }
+
+    /// Validate all entries of the defaults, groups and sinks sections.
+    fn validate_sections(&self) -> Result<(), ConfigError> {
+        self.validate_settings("defaults", &self.defaults)?;
+        for (name, settings) in self.groups.iter() {
+            self.validate_settings(&format!("groups.{}", name), settings)?;
+        }
+        for (name, sink) in self.sinks.iter() {
+            if let SinkConfig::Command { command, .. } = sink {
+                if command.is_empty() {
+                    return Err(invalid_entry(format!("sinks.{}.command", name), "Empty command"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate a single settings entry.
+    fn validate_settings(&self, location: &str, settings: &Settings) -> Result<(), ConfigError> {
+        if let Some(interval) = &settings.interval {
+            parse_interval(interval).map_err(|error| entry_error(format!("{}.interval", location), error))?;
+        }
+        if let Some(schedule) = &settings.schedule {
+            CronSchedule::from_str(schedule).map_err(|error| entry_error(format!("{}.schedule", location), error))?;
+        }
+        for (name, interval) in [
+            ("failing_interval", &settings.failing_interval),
+            ("max_interval", &settings.max_interval),
+        ] {
+            if let Some(interval) = interval {
+                parse_interval(interval).map_err(|error| entry_error(format!("{}.{}", location, name), error))?;
+            }
+        }
+        if let Some(backoff) = settings.backoff {
+            validate_backoff(backoff).map_err(|error| entry_error(format!("{}.backoff", location), error))?;
+        }
+        if let Some(hysteresis) = settings.hysteresis {
+            validate_hysteresis(hysteresis).map_err(|error| entry_error(format!("{}.hysteresis", location), error))?;
+        }
+        if let Some(check_timeout) = &settings.check_timeout {
+            parse_duration(check_timeout).map_err(|error| entry_error(format!("{}.check_timeout", location), error))?;
+        }
+        if let Some(timeout) = &settings.timeout {
+            parse_duration(timeout).map_err(|error| entry_error(format!("{}.timeout", location), error))?;
+        }
+        if let Some(degraded_latency) = &settings.degraded_latency {
+            parse_duration(degraded_latency)
+                .map_err(|error| entry_error(format!("{}.degraded_latency", location), error))?;
+        }
+        if let Some(resolve_policy) = &settings.resolve_policy {
+            parse_resolve_policy(resolve_policy)
+                .map_err(|error| entry_error(format!("{}.resolve_policy", location), error))?;
+        }
+        if let Some(sinks) = &settings.sinks {
+            self.validate_sink_names(location, sinks)?;
+        }
+        if let Some(maintenance) = &settings.maintenance {
+            build_maintenance_windows(location, maintenance)?;
+        }
+        if let Some(flapping) = &settings.flapping {
+            flapping
+                .build()
+                .map_err(|(field, error)| entry_error(format!("{}.flapping.{}", location, field), error))?;
+        }
+        Ok(())
+    }
+
+    /// Validate that all referenced sinks exist.
+    fn validate_sink_names(&self, location: &str, sinks: &[String]) -> Result<(), ConfigError> {
+        match sinks.iter().position(|name| !self.sinks.contains_key(name)) {
+            Some(index) => Err(invalid_entry(format!("{}.sinks[{}]", location, index), "Unknown sink")),
+            None => Ok(()),
+        }
+    }
+
+    /// Build a single [AsyncTarget] from given target entry.
+    fn build_async_target(&self, index: usize, entry: &TargetConfig) -> Result<AsyncTarget<'static>, ConfigError> {
+        let location = format!("targets[{}]", index);
+
+        // Lookup groups and setup settings resolution: target first, groups second, defaults last.
+        let mut groups = Vec::with_capacity(entry.groups.len());
+        for (group_index, name) in entry.groups.iter().enumerate() {
+            match self.groups.get(name) {
+                Some(group) => groups.push(group),
+                None => {
+                    return Err(invalid_entry(
+                        format!("{}.groups[{}]", location, group_index),
+                        "Unknown group",
+                    ))
+                }
+            }
+        }
+        let settings = SettingsChain {
+            groups,
+            defaults: &self.defaults,
+        };
+
+        // Resolve all settings
+        let interval = match settings.resolve(entry.interval.as_ref(), |settings| settings.interval.as_ref()) {
+            Some(interval) => {
+                parse_interval(interval).map_err(|error| entry_error(format!("{}.interval", location), error))?
+            }
+            None => DEFAULT_CHECK_INTERVAL,
+        };
+
+        let schedule = match settings.resolve(entry.schedule.as_ref(), |settings| settings.schedule.as_ref()) {
+            Some(schedule) => Some(CheckSchedule::from(
+                CronSchedule::from_str(schedule)
+                    .map_err(|error| entry_error(format!("{}.schedule", location), error))?,
+            )),
+            None => None,
+        };
+
+        let failing_interval = match settings.resolve(entry.failing_interval.as_ref(), |settings| {
+            settings.failing_interval.as_ref()
+        }) {
+            Some(failing_interval) => parse_interval(failing_interval)
+                .map_err(|error| entry_error(format!("{}.failing_interval", location), error))?,
+            None => interval,
+        };
+
+        let backoff = match settings.resolve(entry.backoff.as_ref(), |settings| settings.backoff.as_ref()) {
+            Some(backoff) => {
+                validate_backoff(*backoff).map_err(|error| entry_error(format!("{}.backoff", location), error))?
+            }
+            None => 1.0,
+        };
+
+        let max_interval =
+            match settings.resolve(entry.max_interval.as_ref(), |settings| settings.max_interval.as_ref()) {
+                Some(max_interval) => parse_interval(max_interval)
+                    .map_err(|error| entry_error(format!("{}.max_interval", location), error))?,
+                None => failing_interval,
+            };
+
+        let timeout = match settings.resolve(entry.timeout.as_ref(), |settings| settings.timeout.as_ref()) {
+            Some(timeout) => {
+                Some(parse_duration(timeout).map_err(|error| entry_error(format!("{}.timeout", location), error))?)
+            }
+            None => None,
+        };
+
+        let check_timeout =
+            match settings.resolve(entry.check_timeout.as_ref(), |settings| settings.check_timeout.as_ref()) {
+                Some(check_timeout) => Some(
+                    parse_duration(check_timeout)
+                        .map_err(|error| entry_error(format!("{}.check_timeout", location), error))?,
+                ),
+                None => None,
+            };
+
+        let degraded_latency = match settings.resolve(entry.degraded_latency.as_ref(), |settings| {
+            settings.degraded_latency.as_ref()
+        }) {
+            Some(degraded_latency) => Some(
+                parse_duration(degraded_latency)
+                    .map_err(|error| entry_error(format!("{}.degraded_latency", location), error))?,
+            ),
+            None => None,
+        };
+
+        let resolve_policy = match settings.resolve(entry.resolve_policy.as_ref(), |settings| {
+            settings.resolve_policy.as_ref()
+        }) {
+            Some(resolve_policy) => Some(
+                parse_resolve_policy(resolve_policy)
+                    .map_err(|error| entry_error(format!("{}.resolve_policy", location), error))?,
+            ),
+            None => None,
+        };
+
+        let retries = settings
+            .resolve(entry.retries.as_ref(), |settings| settings.retries.as_ref())
+            .copied()
+            .unwrap_or(0);
+
+        if let Some(hysteresis) = entry.hysteresis {
+            validate_hysteresis(hysteresis).map_err(|error| entry_error(format!("{}.hysteresis", location), error))?;
+        }
+        let hysteresis = settings
+            .resolve(entry.hysteresis.as_ref(), |settings| settings.hysteresis.as_ref())
+            .copied()
+            .unwrap_or(1);
+
+        if let Some(sinks) = &entry.sinks {
+            self.validate_sink_names(&location, sinks)?;
+        }
+        let sinks = settings
+            .resolve(entry.sinks.as_ref(), |settings| settings.sinks.as_ref())
+            .cloned()
+            .unwrap_or_default();
+
+        let flapping_policy = match settings.resolve(entry.flapping.as_ref(), |settings| settings.flapping.as_ref()) {
+            Some(flapping) => Some(
+                flapping
+                    .build()
+                    .map_err(|(field, error)| entry_error(format!("{}.flapping.{}", location, field), error))?,
+            ),
+            None => None,
+        };
+
+        // Note: In contrast to other settings, maintenance windows are not overridden but combined.
+        let mut maintenance_windows =
+            build_maintenance_windows(&location, entry.maintenance.as_deref().unwrap_or_default())?;
+        for group in settings.groups.iter().chain([&settings.defaults]) {
+            // Note: Windows of groups and defaults were validated already.
+            maintenance_windows.extend(build_maintenance_windows(
+                "",
+                group.maintenance.as_deref().unwrap_or_default(),
+            )?);
+        }
+
+        if let Some(options) = &entry.icmp {
+            if entry.kind != TargetKind::Icmp {
+                return Err(invalid_entry(
+                    format!("{}.icmp", location),
+                    "ICMP options are only supported by ICMP targets",
+                ));
+            }
+            options
+                .validate()
+                .map_err(|(field, error)| entry_error(format!("{}.icmp.{}", location, field), error))?;
+        }
+
+        // Construct target and handler. The fingerprint covers all resolved settings, allowing
+        // AsyncTargetExecutor::reload to detect changed targets.
+        let sinks: Vec<SinkConfig> = sinks.iter().map(|name| self.sinks[name].clone()).collect();
+        let interval_policy = IntervalPolicy::new(interval)
+            .set_failing_interval(failing_interval)
+            .set_backoff(backoff, max_interval);
+        let fingerprint = format!(
+            "{:?}",
+            (
+                entry.kind,
+                &entry.address,
+                (&interval_policy, &schedule),
+                retries,
+                hysteresis,
+                check_timeout,
+                timeout,
+                degraded_latency,
+                &resolve_policy,
+                &entry.icmp,
+                &sinks,
+                (&maintenance_windows, &entry.parents, &flapping_policy)
+            )
+        );
+        let target = build_target(entry, timeout, degraded_latency, resolve_policy)
+            .map_err(|error| ConfigError::from((format!("{}.address", location), error)))?;
+        let handler = build_handler(sinks);
+
+        let mut target = AsyncTarget::new(target, handler, interval)
+            .set_interval_policy(interval_policy)
+            .set_retries(retries)
+            .set_hysteresis(hysteresis)
+            .set_fingerprint(fingerprint);
+        if let Some(check_timeout) = check_timeout {
+            target = target.set_check_timeout(check_timeout);
+        }
+        if let Some(schedule) = schedule {
+            target = target.set_schedule(schedule);
+        }
+        for window in maintenance_windows {
+            target = target.add_maintenance_window(window);
+        }
+        for parent in entry.parents.iter() {
+            target = target.add_parent(parent);
+        }
+        if let Some(flapping_policy) = flapping_policy {
+            target = target.set_flapping_policy(flapping_policy);
+        }
+        Ok(target)
+    }
+}
+
+/// Chain of [Settings] a target inherits from: groups first, defaults last.
+struct SettingsChain<'a> {
+    groups: Vec<&'a Settings>,
+    defaults: &'a Settings,
+}
+
+impl<'a> SettingsChain<'a> {
+    /// Resolve a setting. If the target specifies no value, lookup the setting in the chain.
+    fn resolve<T>(&self, value: Option<&'a T>, select: fn(&'a Settings) -> Option<&'a T>) -> Option<&'a T> {
+        value.or_else(|| {
+            self.groups
+                .iter()
+                .find_map(|group| select(group))
+                .or_else(|| select(self.defaults))
+        })
+    }
+}
+
+/// Load the configuration file at given path and build all configured targets.
+///
+/// # Arguments
+/// * path: path of the configuration file. See [Config::from_file] for supported formats.
+///
+/// # Returns
+/// * On success, a vector of [AsyncTarget]s ready to be handed to
+///   [AsyncTargetExecutor::start](super::AsyncTargetExecutor::start).
+/// * On failure, a [ConfigError].
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<AsyncTarget<'static>>, ConfigError> {
+    Config::from_file(path)?.build_async_targets()
+}
+
+/// Construct the [Target] described by given entry.
+fn build_target(
+    entry: &TargetConfig,
+    timeout: Option<Duration>,
+    degraded_latency: Option<Duration>,
+    resolve_policy: Option<ResolvePolicy>,
+) -> Result<BoxedTarget<'static>, Box<dyn Error>> {
+    match entry.kind {
+        TargetKind::Icmp => {
+            let mut target = IcmpTarget::from_str(&entry.address)?;
+            if let Some(resolve_policy) = resolve_policy {
+                target = target.set_resolve_policy(resolve_policy);
+            }
+            if let Some(timeout) = timeout {
+                target = target.set_echo_timeout(timeout);
+            }
+            if let Some(degraded_latency) = degraded_latency {
+                target = target.set_degraded_rtt(degraded_latency);
+            }
+            if let Some(options) = &entry.icmp {
+                target = options.apply(target);
+            }
+            Ok(Box::new(target))
+        }
+        TargetKind::Tcp => {
+            let mut target = TcpTarget::from_str(&entry.address)?;
+            if let Some(resolve_policy) = resolve_policy {
+                target = target.set_resolve_policy(resolve_policy);
+            }
+            if let Some(timeout) = timeout {
+                target = target.set_connect_timeout(timeout);
+            }
+            if let Some(degraded_latency) = degraded_latency {
+                target = target.set_degraded_latency(degraded_latency);
+            }
+            Ok(Box::new(target))
+        }
+    }
+}
+
+impl FlappingConfig {
+    /// Build the [FlappingPolicy]. On failure, the name of the invalid field is returned as well.
+    fn build(&self) -> Result<FlappingPolicy, (&'static str, ConfigError)> {
+        let window = parse_interval(&self.window).map_err(|error| ("window", error))?;
+        if self.start_threshold == 0 {
+            return Err((
+                "start_threshold",
+                ConfigError::from("Start threshold must be at least 1"),
+            ));
+        }
+        if self.stop_threshold >= self.start_threshold {
+            return Err((
+                "stop_threshold",
+                ConfigError::from("Stop threshold must be below start threshold"),
+            ));
+        }
+        Ok(FlappingPolicy::new(window, self.start_threshold, self.stop_threshold))
+    }
+}
+
+impl IcmpOptions {
+    /// Apply all given options to an [IcmpTarget].
+    fn apply(&self, mut target: IcmpTarget) -> IcmpTarget {
+        if let Some(count) = self.count {
+            target = target.set_echo_count(count);
+        }
+        if let Some(Ok(echo_interval)) = self.echo_interval.as_deref().map(parse_duration) {
+            target = target.set_echo_interval(echo_interval);
+        }
+        if let Some(success_ratio) = self.success_ratio {
+            target = target.set_success_ratio(success_ratio);
+        }
+        if let Some(Ok(max_rtt)) = self.max_rtt.as_deref().map(parse_duration) {
+            target = target.set_max_rtt(max_rtt);
+        }
+        if let Some(Ok(max_jitter)) = self.max_jitter.as_deref().map(parse_duration) {
+            target = target.set_max_jitter(max_jitter);
+        }
+        if let Some(Ok(degraded_jitter)) = self.degraded_jitter.as_deref().map(parse_duration) {
+            target = target.set_degraded_jitter(degraded_jitter);
+        }
+        if let Some(degraded_loss_ratio) = self.degraded_loss_ratio {
+            target = target.set_degraded_loss_ratio(degraded_loss_ratio);
+        }
+        if let Some(ttl) = self.ttl {
+            target = target.set_ttl(ttl);
+        }
+        if let Some(payload_size) = self.payload_size {
+            target = target.set_payload_size(payload_size);
+        }
+        if let Some(dscp) = self.dscp {
+            target = target.set_dscp(dscp);
+        }
+        target
+    }
+
+    /// Validate all given options.
+    fn validate(&self) -> Result<(), (&'static str, ConfigError)> {
+        if self.count == Some(0) {
+            return Err(("count", ConfigError::from("Count must be at least 1")));
+        }
+        if let Some(success_ratio) = self.success_ratio {
+            if !(0.0..=1.0).contains(&success_ratio) {
+                return Err((
+                    "success_ratio",
+                    ConfigError::from("Success ratio must be within 0.0 and 1.0"),
+                ));
+            }
+        }
+        if let Some(degraded_loss_ratio) = self.degraded_loss_ratio {
+            if !(0.0..=1.0).contains(&degraded_loss_ratio) {
+                return Err((
+                    "degraded_loss_ratio",
+                    ConfigError::from("Degraded loss ratio must be within 0.0 and 1.0"),
+                ));
+            }
+        }
+        if let Some(dscp) = self.dscp {
+            if dscp > 0x3f {
+                return Err(("dscp", ConfigError::from("DSCP must be within 0 and 63")));
+            }
+        }
+
+        let durations = [
+            ("echo_interval", &self.echo_interval),
+            ("max_rtt", &self.max_rtt),
+            ("max_jitter", &self.max_jitter),
+            ("degraded_jitter", &self.degraded_jitter),
+        ];
+        for (field, duration) in durations {
+            if let Some(duration) = duration {
+                parse_duration(duration).map_err(|error| (field, error))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Construct a handler notifying all given sinks.
+fn build_handler(sinks: Vec<SinkConfig>) -> BoxedHandler<'static> {
+    Box::new(
+        move |target: &dyn Target, status: Status, old_status: Status, error: Option<CheckTargetError>| {
+            for sink in sinks.iter() {
+                sink.notify(target, &status, &old_status, error.as_ref());
+            }
+        },
+    )
+}
+
+impl SinkConfig {
+    /// Notify this sink about the result of an availability check.
+    fn notify(&self, target: &dyn Target, status: &Status, old_status: &Status, error: Option<&CheckTargetError>) {
+        match self {
+            SinkConfig::Stdout { transitions_only } => {
+                if *transitions_only && status.same_state(old_status) {
+                    return;
+                }
+
+                print!("Target \"{}\"", target.get_id());
+                print!(", old status \"{}\"", old_status);
+                print!(", new status \"{}\"", status);
+                match error {
+                    None => println!(),
+                    Some(err) => println!(", Error: \"{}\"", err),
+                }
+            }
+            SinkConfig::Command {
+                command,
+                args,
+                transitions_only,
+            } => {
+                if *transitions_only && status.same_state(old_status) {
+                    return;
+                }
+
+                // Note: A failing sink must not disturb the remaining sinks. Errors are ignored.
+                // The command must not block the handler, it is awaited by a separate thread.
+                let child = Command::new(command)
+                    .args(args)
+                    .stdin(Stdio::null())
+                    .env("REACHABLE_TARGET", target.get_id())
+                    .env("REACHABLE_STATUS", status.to_string())
+                    .env("REACHABLE_OLD_STATUS", old_status.to_string())
+                    .env("REACHABLE_ERROR", error.map(|err| err.to_string()).unwrap_or_default())
+                    .spawn();
+                if let Ok(child) = child {
+                    thread::spawn(move || reap(child, COMMAND_SINK_TIMEOUT));
+                }
+            }
+        }
+    }
+}
+
+/// Wait for given child process to exit. If it is still running after given timeout, it is killed.
+fn reap(mut child: Child, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    while let Ok(None) = child.try_wait() {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Parse a [ResolvePolicy] from its configuration name: "agnostic", "ipv4" or "ipv6".
+fn parse_resolve_policy(s: &str) -> Result<ResolvePolicy, ConfigError> {
+    match s {
+        "agnostic" => Ok(ResolvePolicy::Agnostic),
+        "ipv4" => Ok(ResolvePolicy::ResolveToIPv4),
+        "ipv6" => Ok(ResolvePolicy::ResolveToIPv6),
+        _ => Err(ConfigError::from(
+            "Unknown resolve policy, expected agnostic, ipv4 or ipv6",
+        )),
+    }
+}
+
+/// Validate a backoff factor. Factors below 1 would shorten the interval of unavailable targets.
+fn validate_backoff(backoff: f64) -> Result<f64, ConfigError> {
+    match backoff >= 1.0 {
+        true => Ok(backoff),
+        false => Err(ConfigError::from("Backoff must be at least 1")),
+    }
+}
+
+/// Validate a hysteresis, at least one check is required to change the status.
+fn validate_hysteresis(hysteresis: u32) -> Result<u32, ConfigError> {
+    match hysteresis >= 1 {
+        true => Ok(hysteresis),
+        false => Err(ConfigError::from("Hysteresis must be at least 1")),
+    }
+}
+
+/// Parse a check interval. In contrast to other durations, intervals must not be zero.
+fn parse_interval(s: &str) -> Result<Duration, ConfigError> {
+    match parse_duration(s)? {
+        interval if interval.is_zero() => Err(ConfigError::from("Interval must not be zero")),
+        interval => Ok(interval),
+    }
+}
+
+/// Parse a [Duration] from a number followed by a unit: "ms", "s", "m" or "h". e.g. "250ms", "10s".
+///
+/// # Errors
+/// Returns a [ConfigError] if the number or unit is invalid or the duration is too large.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use reachable::config::parse_duration;
+/// assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, ConfigError> {
+    let s = s.trim();
+    let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(index);
+
+    let value: u64 = value
+        .parse()
+        .map_err(|error| ConfigError::from(("Failed to parse duration", boxed(error))))?;
+
+    let seconds = |factor: u64| {
+        value
+            .checked_mul(factor)
+            .map(Duration::from_secs)
+            .ok_or(ConfigError::from("Duration is too large"))
+    };
+    match unit.trim() {
+        "ms" => Ok(Duration::from_millis(value)),
+        "s" => Ok(Duration::from_secs(value)),
+        "m" => seconds(60),
+        "h" => seconds(60 * 60),
+        _ => Err(ConfigError::from("Unknown duration unit, expected ms, s, m or h")),
+    }
+}
+
+/// Build the [MaintenanceWindow]s of given entries, located at "{location}.maintenance[index]".
+fn build_maintenance_windows(
+    location: &str,
+    entries: &[MaintenanceConfig],
+) -> Result<Vec<MaintenanceWindow>, ConfigError> {
+    let mut windows = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let location = format!("{}.maintenance[{}]", location, index);
+        let window = match (&entry.start, &entry.end, &entry.cron, &entry.duration) {
+            (Some(start), Some(end), None, None) => {
+                let start = parse_utc_time(start).map_err(|error| entry_error(format!("{}.start", location), error))?;
+                let end = parse_utc_time(end).map_err(|error| entry_error(format!("{}.end", location), error))?;
+                if end <= start {
+                    return Err(invalid_entry(location, "End must be after start"));
+                }
+                MaintenanceWindow::Once { start, end }
+            }
+            (None, None, Some(cron), Some(duration)) => {
+                let schedule =
+                    CronSchedule::from_str(cron).map_err(|error| entry_error(format!("{}.cron", location), error))?;
+                let duration =
+                    parse_interval(duration).map_err(|error| entry_error(format!("{}.duration", location), error))?;
+                MaintenanceWindow::Recurring { schedule, duration }
+            }
+            _ => {
+                return Err(invalid_entry(
+                    location,
+                    "Maintenance requires either start and end or cron and duration",
+                ))
+            }
+        };
+        windows.push(window);
+    }
+    Ok(windows)
+}
+
+fn boxed<E: Error + 'static>(error: E) -> Box<dyn Error> {
+    Box::new(error)
+}
+
+fn entry_error<E: Error + 'static>(location: String, error: E) -> ConfigError {
+    ConfigError::from((location, boxed(error)))
+}
+
+fn invalid_entry(location: String, message: &'static str) -> ConfigError {
+    entry_error(location, ConfigError::from(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML_CONFIG: &str = r#"
+        [defaults]
+        interval = "5s"
+        sinks = ["console"]
+
+        [groups.databases]
+        interval = "30s"
+        failing_interval = "5s"
+        backoff = 2.0
+        retries = 2
+        hysteresis = 3
+        check_timeout = "10s"
+
+        [sinks.console]
+        type = "stdout"
+
+        [[targets]]
+        kind = "icmp"
+        address = "127.0.0.1"
+
+        [[targets]]
+        kind = "tcp"
+        address = "127.0.0.1:5432"
+        groups = ["databases"]
+        hysteresis = 2
+        timeout = "250ms"
+    "#;
+
+    const YAML_CONFIG: &str = r#"
+        defaults:
+          interval: 5s
+          sinks: [console]
+        groups:
+          databases:
+            interval: 30s
+            failing_interval: 5s
+            backoff: 2.0
+            retries: 2
+            hysteresis: 3
+            check_timeout: 10s
+        sinks:
+          console:
+            type: stdout
+        targets:
+          - kind: icmp
+            address: 127.0.0.1
+          - kind: tcp
+            address: 127.0.0.1:5432
+            groups: [databases]
+            hysteresis: 2
+            timeout: 250ms
+    "#;
+
+    fn verify_targets(targets: Vec<AsyncTarget<'static>>) {
+        assert_eq!(targets.len(), 2);
+
+        // ICMP target inherits from defaults
+        assert_eq!(targets[0].get_id(), "127.0.0.1");
+        assert_eq!(targets[0].get_check_interval(), &Duration::from_secs(5));
+        assert_eq!(targets[0].get_retries(), 0);
+        assert_eq!(targets[0].get_hysteresis(), 1);
+        assert_eq!(targets[0].get_check_timeout(), None);
+        assert_eq!(
+            targets[0].get_interval_policy(),
+            &IntervalPolicy::new(Duration::from_secs(5))
+        );
+
+        // TCP target inherits from group, but overrides hysteresis
+        assert_eq!(targets[1].get_id(), "127.0.0.1:5432");
+        assert_eq!(targets[1].get_check_interval(), &Duration::from_secs(30));
+        assert_eq!(targets[1].get_retries(), 2);
+        assert_eq!(targets[1].get_hysteresis(), 2);
+        assert_eq!(targets[1].get_check_timeout(), Some(&Duration::from_secs(10)));
+        assert_eq!(
+            targets[1].get_interval_policy(),
+            &IntervalPolicy::new(Duration::from_secs(30))
+                .set_failing_interval(Duration::from_secs(5))
+                .set_backoff(2.0, Duration::from_secs(5))
+        );
+    }
+
+    fn build_error(content: &str) -> String {
+        format!(
+            "{}",
+            Config::from_toml_str(content)
+                .unwrap()
+                .build_async_targets()
+                .err()
+                .unwrap()
+        )
+    }
+
+    #[test]
+    fn config_from_toml() {
+        // Expectency: A valid TOML configuration must result in the configured targets, settings
+        //             are resolved from target over groups to defaults.
+        verify_targets(
+            Config::from_toml_str(TOML_CONFIG)
+                .unwrap()
+                .build_async_targets()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn config_from_yaml() {
+        // Expectency: A valid YAML configuration must result in the same targets as the
+        //             equivalent TOML configuration.
+        assert_eq!(
+            Config::from_yaml_str(YAML_CONFIG).unwrap(),
+            Config::from_toml_str(TOML_CONFIG).unwrap()
+        );
+        verify_targets(
+            Config::from_yaml_str(YAML_CONFIG)
+                .unwrap()
+                .build_async_targets()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn config_from_file_unsupported_extension() {
+        // Expectency: Files without a known extension must be rejected.
+        assert_eq!(
+            format!("{}", Config::from_file("monitoring.ini").unwrap_err()),
+            "Unsupported file extension, expected toml, yaml or yml"
+        );
+    }
+
+    #[test]
+    fn config_syntax_error() {
+        // Expectency: Unknown fields must be reported as error.
+        let error = Config::from_toml_str("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nport = 12\n").unwrap_err();
+        assert!(format!("{}", error).starts_with("Failed to parse TOML caused by: unknown field `port`"));
+    }
+
+    #[test]
+    fn config_invalid_address() {
+        // Expectency: An invalid target address must point at the offending entry.
+        assert_eq!(
+            build_error(
+                "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n[[targets]]\nkind = \"tcp\"\naddress = \"foo:0\"\n"
+            ),
+            "Invalid entry 'targets[1].address' caused by: Invalid Portnumber '0' found"
+        );
+    }
+
+    #[test]
+    fn config_invalid_interval() {
+        // Expectency: Invalid durations must point at the offending entry.
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\ninterval = \"5x\"\n"),
+            "Invalid entry 'targets[0].interval' caused by: Unknown duration unit, expected ms, s, m or h"
+        );
+        assert_eq!(
+            build_error("[groups.slow]\ninterval = \"0s\"\n"),
+            "Invalid entry 'groups.slow.interval' caused by: Interval must not be zero"
+        );
+        assert_eq!(
+            build_error("[groups.slow]\nfailing_interval = \"0s\"\n"),
+            "Invalid entry 'groups.slow.failing_interval' caused by: Interval must not be zero"
+        );
+        assert_eq!(
+            build_error("[defaults]\nbackoff = 0.5\n"),
+            "Invalid entry 'defaults.backoff' caused by: Backoff must be at least 1"
+        );
+        assert_eq!(
+            build_error("[defaults]\nhysteresis = 0\n[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n"),
+            "Invalid entry 'defaults.hysteresis' caused by: Hysteresis must be at least 1"
+        );
+        assert_eq!(
+            build_error("[groups.slow]\nhysteresis = 0\n"),
+            "Invalid entry 'groups.slow.hysteresis' caused by: Hysteresis must be at least 1"
+        );
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nhysteresis = 0\n"),
+            "Invalid entry 'targets[0].hysteresis' caused by: Hysteresis must be at least 1"
+        );
+    }
+
+    #[test]
+    fn config_unknown_references() {
+        // Expectency: References to unknown groups and sinks must point at the offending entry.
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\ngroups = [\"missing\"]\n"),
+            "Invalid entry 'targets[0].groups[0]' caused by: Unknown group"
+        );
+        assert_eq!(
+            build_error("[defaults]\nsinks = [\"missing\"]\n"),
+            "Invalid entry 'defaults.sinks[0]' caused by: Unknown sink"
+        );
+    }
+
+    #[test]
+    fn config_icmp_options() {
+        // Expectency: ICMP options must be validated and are rejected for other kinds of targets.
+        assert!(Config::from_toml_str(
+            "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nicmp = { count = 3, success_ratio = 0.5, dscp = 46 }\n"
+        )
+        .unwrap()
+        .build_async_targets()
+        .is_ok());
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nicmp = { dscp = 64 }\n"),
+            "Invalid entry 'targets[0].icmp.dscp' caused by: DSCP must be within 0 and 63"
+        );
+        for ratio in ["1.5", "-0.1", "nan"] {
+            assert_eq!(
+                build_error(&format!(
+                    "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nicmp = {{ degraded_loss_ratio = {} }}\n",
+                    ratio
+                )),
+                "Invalid entry 'targets[0].icmp.degraded_loss_ratio' caused by: Degraded loss ratio must be within 0.0 and 1.0"
+            );
+        }
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nicmp = { max_rtt = \"fast\" }\n"),
+            "Invalid entry 'targets[0].icmp.max_rtt' caused by: Failed to parse duration caused by: \
+             cannot parse integer from empty string"
+        );
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"tcp\"\naddress = \"::1:22\"\nicmp = { count = 3 }\n"),
+            "Invalid entry 'targets[0].icmp' caused by: ICMP options are only supported by ICMP targets"
+        );
+    }
+
+    #[test]
+    fn config_duplicate_target() {
+        // Expectency: Targets must be unique.
+        assert_eq!(
+            build_error(
+                "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n"
+            ),
+            "Invalid entry 'targets[1].address' caused by: Duplicate target"
+        );
+    }
+
+    #[test]
+    fn config_parents() {
+        // Expectency: Parents are added to the target, unknown parents and cycles must point at
+        //             the offending entry.
+        let targets = Config::from_toml_str(
+            "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n\
+             [[targets]]\nkind = \"tcp\"\naddress = \"::1:22\"\nparents = [\"::1\"]\n",
+        )
+        .unwrap()
+        .build_async_targets()
+        .unwrap();
+        assert_eq!(targets[1].get_parents(), &vec![String::from("::1")]);
+
+        assert_eq!(
+            build_error("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nparents = [\"::2\"]\n"),
+            "Invalid entry 'targets[0].parents' caused by: Unknown parent '::2' of target '::1'"
+        );
+        assert_eq!(
+            build_error(
+                "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nparents = [\"::1:22\"]\n\
+                 [[targets]]\nkind = \"tcp\"\naddress = \"::1:22\"\nparents = [\"::1\"]\n"
+            ),
+            "Invalid entry 'targets[0].parents' caused by: Dependency cycle '::1' -> '::1:22' -> '::1'"
+        );
+    }
+
+    #[test]
+    fn config_flapping() {
+        // Expectency: A flapping table results in a flapping policy, invalid thresholds must
+        //             point at the offending field.
+        let targets = Config::from_toml_str(
+            "[defaults]\nflapping = { window = \"10m\", start_threshold = 4, stop_threshold = 1 }\n\
+             [[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n",
+        )
+        .unwrap()
+        .build_async_targets()
+        .unwrap();
+        assert_eq!(
+            targets[0].get_flapping_policy(),
+            Some(&FlappingPolicy::new(Duration::from_secs(600), 4, 1))
+        );
+        assert_eq!(
+            build_error("[groups.a]\nflapping = { window = \"10m\", start_threshold = 2, stop_threshold = 2 }\n"),
+            "Invalid entry 'groups.a.flapping.stop_threshold' caused by: Stop threshold must be below start threshold"
+        );
+    }
+
+    #[test]
+    fn config_schedule() {
+        // Expectency: A schedule is parsed as cron expression, invalid expressions must point at
+        //             the offending entry.
+        let targets =
+            Config::from_toml_str("[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nschedule = \"*/5 * * * *\"\n")
+                .unwrap()
+                .build_async_targets()
+                .unwrap();
+        assert_eq!(
+            targets[0].get_schedule(),
+            Some(&CheckSchedule::from(CronSchedule::from_str("*/5 * * * *").unwrap()))
+        );
+        assert_eq!(
+            build_error("[defaults]\nschedule = \"@never\"\n"),
+            "Invalid entry 'defaults.schedule' caused by: Cron expression requires five fields"
+        );
+    }
+
+    #[test]
+    fn config_maintenance() {
+        // Expectency: Maintenance windows of target, groups and defaults are combined, invalid
+        //             windows must point at the offending entry.
+        let targets = Config::from_toml_str(
+            "[defaults]\nmaintenance = [{ cron = \"0 1 * * *\", duration = \"2h\" }]\n\
+             [[targets]]\nkind = \"icmp\"\naddress = \"::1\"\n\
+             maintenance = [{ start = \"2021-10-03T22:00\", end = \"2021-10-04 02:00:00Z\" }]\n",
+        )
+        .unwrap()
+        .build_async_targets()
+        .unwrap();
+        let windows = targets[0].get_maintenance_windows();
+        assert_eq!(windows.len(), 2);
+        assert!(matches!(windows[0], MaintenanceWindow::Once { .. }));
+        assert!(matches!(windows[1], MaintenanceWindow::Recurring { .. }));
+
+        assert_eq!(
+            build_error("[defaults]\nmaintenance = [{ cron = \"0 1 * * *\" }]\n"),
+            "Invalid entry 'defaults.maintenance[0]' caused by: Maintenance requires either start and end or cron and duration"
+        );
+        assert_eq!(
+            build_error("[groups.db]\nmaintenance = [{ start = \"2021-10-04T02:00\", end = \"2021-10-03T22:00\" }]\n"),
+            "Invalid entry 'groups.db.maintenance[0]' caused by: End must be after start"
+        );
+        assert!(build_error(
+            "[[targets]]\nkind = \"icmp\"\naddress = \"::1\"\nmaintenance = [{ cron = \"61 * * * *\", duration = \"1h\" }]\n"
+        )
+        .starts_with("Invalid entry 'targets[0].maintenance[0].cron' caused by: "));
+        assert!(build_error(
+            "[defaults]\nmaintenance = [{ start = \"300000000000-01-01T00:00\", end = \"2021-10-03T22:00\" }]\n"
+        )
+        .starts_with("Invalid entry 'defaults.maintenance[0].start' caused by: "));
+    }
+
+    #[test]
+    fn config_fingerprint() {
+        // Expectency: Targets built from equal entries share a fingerprint, changes to resolved
+        //             settings (here inherited from defaults) change the fingerprint.
+        let build = |content: &str| Config::from_toml_str(content).unwrap().build_async_targets().unwrap();
+        let target = "[[targets]]\nkind = \"tcp\"\naddress = \"127.0.0.1:22\"\n";
+
+        let first = build(target);
+        let second = build(target);
+        let changed = build(&format!("[defaults]\ninterval = \"1m\"\n{}", target));
+        assert!(first[0].get_fingerprint().is_some());
+        assert_eq!(first[0].get_fingerprint(), second[0].get_fingerprint());
+        assert_ne!(first[0].get_fingerprint(), changed[0].get_fingerprint());
+    }
+
+    #[test]
+    fn config_parse_duration() {
+        // Expectency: Durations support the units ms, s, m and h. Overflowing durations are errors.
+        assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse_duration("10s").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10").is_err());
+        assert_eq!(
+            parse_duration("99999999999999999h").unwrap_err().to_string(),
+            "Duration is too large"
+        );
+        assert!(parse_duration("99999999999999999999s").is_err());
+    }
+
+    #[test]
+    fn config_command_sink_background() {
+        // Expectency: A command sink does not block the handler. A command running longer than
+        //             the timeout is killed.
+        let sink = SinkConfig::Command {
+            command: String::from("sleep"),
+            args: vec![String::from("5")],
+            transitions_only: false,
+        };
+        let target = TcpTarget::from_str("127.0.0.1:22").unwrap();
+        let start = Instant::now();
+        sink.notify(&target, &Status::Available, &Status::Unknown, None);
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let child = Command::new("sleep").arg("5").spawn().unwrap();
+        let start = Instant::now();
+        reap(child, Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
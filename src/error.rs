@@ -158,12 +158,16 @@ pub enum CheckTargetError {
     ResolveTargetError(ErrorMessage, ResolveTargetError),
     /// CheckTargetError containing a Message and a trait object implementing [Error]
     GenericError(ErrorMessage, Box<dyn Error>),
+    /// CheckTargetError containing the message of a panic, caught during the check
+    Panic(String),
+    /// CheckTargetError containing the id of a parent, the target is unreachable due to
+    UnreachableParent(String),
 }
 
 impl Error for CheckTargetError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            CheckTargetError::Message(_) => None,
+            CheckTargetError::Message(_) | CheckTargetError::Panic(_) | CheckTargetError::UnreachableParent(_) => None,
             CheckTargetError::ResolveTargetError(_, ref error) => Some(error),
             CheckTargetError::GenericError(_, ref error) => Some(error.as_ref()),
         }
@@ -175,7 +179,9 @@ impl fmt::Display for CheckTargetError {
         let error_message = match self {
             CheckTargetError::Message(error_message)
             | CheckTargetError::ResolveTargetError(error_message, _)
-            | CheckTargetError::GenericError(error_message, _) => error_message,
+            | CheckTargetError::GenericError(error_message, _) => String::from(*error_message),
+            CheckTargetError::Panic(message) => format!("Check panicked: {}", message),
+            CheckTargetError::UnreachableParent(parent) => format!("Unreachable due to parent '{}'", parent),
         };
 
         match self.source() {
@@ -217,10 +223,313 @@ impl From<Box<dyn Error>> for CheckTargetError {
     }
 }
 
+/// Custom error type for a failed attempt to parse a schedule, e.g. a cron expression.
+#[derive(Debug)]
+pub enum ParseScheduleError {
+    /// ParseScheduleError containing a Message
+    Message(ErrorMessage),
+    /// ParseScheduleError containing a Message and a [num::ParseIntError]
+    ParseIntError(ErrorMessage, num::ParseIntError),
+}
+
+impl Error for ParseScheduleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseScheduleError::Message(_) => None,
+            ParseScheduleError::ParseIntError(_, ref error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for ParseScheduleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let error_message = match self {
+            ParseScheduleError::Message(error_message) | ParseScheduleError::ParseIntError(error_message, _) => {
+                error_message
+            }
+        };
+
+        match self.source() {
+            None => write!(formatter, "{}", error_message),
+            Some(error) => write!(formatter, "{} caused by: {}", error_message, error),
+        }
+    }
+}
+
+impl From<ErrorMessage> for ParseScheduleError {
+    fn from(message: ErrorMessage) -> Self {
+        ParseScheduleError::Message(message)
+    }
+}
+
+impl From<(ErrorMessage, num::ParseIntError)> for ParseScheduleError {
+    fn from(pieces: (ErrorMessage, num::ParseIntError)) -> Self {
+        let (msg, error) = pieces;
+        ParseScheduleError::ParseIntError(msg, error)
+    }
+}
+
+/// Custom error type for invalid ids or dependencies of targets, detected on registration.
+#[cfg(feature = "async")]
+#[derive(Debug, PartialEq)]
+pub enum DependencyError {
+    /// DependencyError containing the id of a target and the id of its parent, that is not
+    /// part of the registered targets.
+    UnknownParent(String, String),
+    /// DependencyError containing the ids of all targets forming a cycle, starting and ending
+    /// with the same target.
+    Cycle(Vec<String>),
+    /// DependencyError containing the id shared by multiple targets.
+    DuplicateId(String),
+}
+
+#[cfg(feature = "async")]
+impl Error for DependencyError {}
+
+#[cfg(feature = "async")]
+impl fmt::Display for DependencyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DependencyError::UnknownParent(id, parent) => {
+                write!(formatter, "Unknown parent '{}' of target '{}'", parent, id)
+            }
+            DependencyError::Cycle(ids) => write!(formatter, "Dependency cycle '{}'", ids.join("' -> '")),
+            DependencyError::DuplicateId(id) => write!(formatter, "Duplicate target '{}'", id),
+        }
+    }
+}
+
+/// Custom error type for a failed attempt to load a monitoring configuration.
+#[cfg(feature = "config")]
+#[derive(Debug)]
+pub enum ConfigError {
+    /// ConfigError containing a Message
+    Message(ErrorMessage),
+    /// ConfigError containing a Message and an [io::Error]
+    IoError(ErrorMessage, io::Error),
+    /// ConfigError containing a Message and a trait object implementing [Error]
+    GenericError(ErrorMessage, Box<dyn Error>),
+    /// ConfigError containing the location of an invalid configuration entry and the trait object
+    /// implementing [Error] describing why the entry is invalid.
+    InvalidEntry(String, Box<dyn Error>),
+}
+
+#[cfg(feature = "config")]
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Message(_) => None,
+            ConfigError::IoError(_, ref error) => Some(error),
+            ConfigError::GenericError(_, ref error) => Some(error.as_ref()),
+            ConfigError::InvalidEntry(_, ref error) => Some(error.as_ref()),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl fmt::Display for ConfigError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let error_message = match self {
+            ConfigError::Message(error_message)
+            | ConfigError::IoError(error_message, _)
+            | ConfigError::GenericError(error_message, _) => String::from(*error_message),
+            ConfigError::InvalidEntry(entry, _) => format!("Invalid entry '{}'", entry),
+        };
+
+        match self.source() {
+            None => write!(formatter, "{}", error_message),
+            Some(error) => write!(formatter, "{} caused by: {}", error_message, error),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<ErrorMessage> for ConfigError {
+    fn from(message: ErrorMessage) -> Self {
+        ConfigError::Message(message)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<(ErrorMessage, io::Error)> for ConfigError {
+    fn from(pieces: (ErrorMessage, io::Error)) -> Self {
+        let (msg, error) = pieces;
+        ConfigError::IoError(msg, error)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::from(("IoError", error))
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<(ErrorMessage, Box<dyn Error>)> for ConfigError {
+    fn from(pieces: (ErrorMessage, Box<dyn Error>)) -> Self {
+        let (msg, error) = pieces;
+        ConfigError::GenericError(msg, error)
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<Box<dyn Error>> for ConfigError {
+    fn from(error: Box<dyn Error>) -> Self {
+        ConfigError::from(("GenericError", error))
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<(String, Box<dyn Error>)> for ConfigError {
+    fn from(pieces: (String, Box<dyn Error>)) -> Self {
+        let (entry, error) = pieces;
+        ConfigError::InvalidEntry(entry, error)
+    }
+}
+
+/// Custom error type for a failed attempt to setup a webhook or to deliver a notification.
+#[cfg(feature = "webhook")]
+#[derive(Debug)]
+pub enum WebhookError {
+    /// WebhookError containing a Message
+    Message(ErrorMessage),
+    /// WebhookError containing a Message and an [io::Error]
+    IoError(ErrorMessage, io::Error),
+    /// WebhookError containing the unexpected HTTP status code returned by the webhook.
+    HttpStatus(u16),
+}
+
+#[cfg(feature = "webhook")]
+impl Error for WebhookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WebhookError::Message(_) | WebhookError::HttpStatus(_) => None,
+            WebhookError::IoError(_, ref error) => Some(error),
+        }
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl fmt::Display for WebhookError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let error_message = match self {
+            WebhookError::Message(error_message) | WebhookError::IoError(error_message, _) => {
+                String::from(*error_message)
+            }
+            WebhookError::HttpStatus(status) => format!("Unexpected HTTP status {}", status),
+        };
+
+        match self.source() {
+            None => write!(formatter, "{}", error_message),
+            Some(error) => write!(formatter, "{} caused by: {}", error_message, error),
+        }
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl From<ErrorMessage> for WebhookError {
+    fn from(message: ErrorMessage) -> Self {
+        WebhookError::Message(message)
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl From<(ErrorMessage, io::Error)> for WebhookError {
+    fn from(pieces: (ErrorMessage, io::Error)) -> Self {
+        let (msg, error) = pieces;
+        WebhookError::IoError(msg, error)
+    }
+}
+
+#[cfg(feature = "webhook")]
+impl From<io::Error> for WebhookError {
+    fn from(error: io::Error) -> Self {
+        WebhookError::from(("IoError", error))
+    }
+}
+
+/// Custom error type for a failed access on the check history.
+#[cfg(feature = "history")]
+#[derive(Debug)]
+pub enum HistoryError {
+    /// HistoryError containing a Message
+    Message(ErrorMessage),
+    /// HistoryError containing a Message and an [io::Error]
+    IoError(ErrorMessage, io::Error),
+    /// HistoryError containing the line number of a record that could not be parsed.
+    InvalidRecord(usize),
+}
+
+#[cfg(feature = "history")]
+impl Error for HistoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HistoryError::Message(_) | HistoryError::InvalidRecord(_) => None,
+            HistoryError::IoError(_, ref error) => Some(error),
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+impl fmt::Display for HistoryError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let error_message = match self {
+            HistoryError::Message(error_message) | HistoryError::IoError(error_message, _) => {
+                String::from(*error_message)
+            }
+            HistoryError::InvalidRecord(line) => format!("Invalid record in line {}", line),
+        };
+
+        match self.source() {
+            None => write!(formatter, "{}", error_message),
+            Some(error) => write!(formatter, "{} caused by: {}", error_message, error),
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+impl From<ErrorMessage> for HistoryError {
+    fn from(message: ErrorMessage) -> Self {
+        HistoryError::Message(message)
+    }
+}
+
+#[cfg(feature = "history")]
+impl From<(ErrorMessage, io::Error)> for HistoryError {
+    fn from(pieces: (ErrorMessage, io::Error)) -> Self {
+        let (msg, error) = pieces;
+        HistoryError::IoError(msg, error)
+    }
+}
+
+#[cfg(feature = "history")]
+impl From<io::Error> for HistoryError {
+    fn from(error: io::Error) -> Self {
+        HistoryError::from(("IoError", error))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ParseScheduleError tests
+    #[test]
+    fn parse_schedule_error_from_parse_int_error() {
+        // Expectency: A ParseScheduleError must contain its error message and the description
+        //             of the inner ParseIntError.
+        let error = "x".parse::<u32>().unwrap_err();
+        assert_eq!(
+            format!("{}", ParseScheduleError::from(("Invalid minute", error))),
+            "Invalid minute caused by: invalid digit found in string"
+        );
+        assert_eq!(
+            format!("{}", ParseScheduleError::from("Empty schedule")),
+            "Empty schedule"
+        );
+    }
+
     // ParseTargetError tests
     #[test]
     fn parse_target_error_from_str() {
@@ -235,7 +544,7 @@ mod tests {
     fn parse_target_error_from_parse_int_error() {
         // Expectency: A ParseTargetError must contain its error message and the description
         //             of the inner ParseIntError.
-        let error = i32::from_str_radix("invalid", 10).unwrap_err();
+        let error = "invalid".parse::<i32>().unwrap_err();
         assert_eq!(
             format!("{}", ParseTargetError::from(("ParseIntError!", error))),
             "ParseIntError! caused by: invalid digit found in string"
@@ -328,6 +637,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_target_error_panic() {
+        // Expectency: A CheckTargetError caused by a panic must contain the panic message.
+        assert_eq!(
+            format!("{}", CheckTargetError::Panic(String::from("Boom"))),
+            "Check panicked: Boom"
+        );
+    }
+
+    #[test]
+    fn check_target_error_unreachable_parent() {
+        // Expectency: A CheckTargetError caused by an unreachable parent must name the parent.
+        assert_eq!(
+            format!("{}", CheckTargetError::UnreachableParent(String::from("gateway"))),
+            "Unreachable due to parent 'gateway'"
+        );
+    }
+
     #[test]
     fn check_target_error_via_questionmark_operator() {
         // Expectency: Ensure conversion via Questionmark operator: Construct ResolveTargetError
@@ -346,4 +673,69 @@ mod tests {
             "ResolveTargetError caused by: IoError caused by: timed out"
         );
     }
+
+    // ConfigError tests
+    #[cfg(feature = "config")]
+    #[test]
+    fn config_error_from_str() {
+        // Expectency: A ConfigError must contain its error message.
+        assert_eq!(format!("{}", ConfigError::from("Error Message!")), "Error Message!");
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn config_error_invalid_entry() {
+        // Expectency: A ConfigError pointing at an invalid entry must contain the entry location
+        //             and the description of the inner error.
+        let error: Box<dyn Error> = Box::new(ParseTargetError::from("No FQHN found"));
+        assert_eq!(
+            format!("{}", ConfigError::from((String::from("targets[3].address"), error))),
+            "Invalid entry 'targets[3].address' caused by: No FQHN found"
+        );
+    }
+
+    // DependencyError tests
+    #[cfg(feature = "async")]
+    #[test]
+    fn dependency_error_cycle() {
+        // Expectency: A DependencyError caused by a cycle must contain all targets of the cycle.
+        let cycle = vec![String::from("a"), String::from("b"), String::from("a")];
+        assert_eq!(
+            format!("{}", DependencyError::Cycle(cycle)),
+            "Dependency cycle 'a' -> 'b' -> 'a'"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                DependencyError::UnknownParent(String::from("a"), String::from("c"))
+            ),
+            "Unknown parent 'c' of target 'a'"
+        );
+        assert_eq!(
+            format!("{}", DependencyError::DuplicateId(String::from("a"))),
+            "Duplicate target 'a'"
+        );
+    }
+
+    // WebhookError tests
+    #[cfg(feature = "webhook")]
+    #[test]
+    fn webhook_error_http_status() {
+        // Expectency: A WebhookError caused by an unexpected response must contain the status code.
+        assert_eq!(
+            format!("{}", WebhookError::HttpStatus(503)),
+            "Unexpected HTTP status 503"
+        );
+    }
+
+    // HistoryError tests
+    #[cfg(feature = "history")]
+    #[test]
+    fn history_error_invalid_record() {
+        // Expectency: A HistoryError caused by an invalid record must contain its line number.
+        assert_eq!(
+            format!("{}", HistoryError::InvalidRecord(7)),
+            "Invalid record in line 7"
+        );
+    }
 }
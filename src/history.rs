@@ -0,0 +1,564 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains a persistent history of the checks of an [AsyncTargetExecutor], offering
+//! uptime reports.
+//!
+//! The [HistoryStore] is a [CheckObserver] appending each check result and each status
+//! transition as a line to a file. Each line consists of tab separated fields:
+//! * Check: `<unix time in ms> check <id> <status> <detail>`
+//! * Check during a maintenance window: `<unix time in ms> maintenance <id> <status> <detail>`
+//! * Transition: `<unix time in ms> transition <id> <status> <old status>`
+//!
+//! Status values are "unknown", "available", "degraded" and "not_available". The detail contains
+//! the reason of a degradation or the message of an error.
+//!
+//! A record cut short, e.g. by a crash while writing, is skipped on reading. This includes records
+//! cut within a multi-byte character. On opening, a
+//! history ending with such a record is terminated by a line break, before new records are
+//! appended.
+//!
+//! # Reporting
+//! Reports are computed from the recorded checks. The time between two consecutive checks of a
+//! target is attributed to the status of the earlier check, the time after the last check up to
+//! now is attributed to the status of the last check. Time in status "unknown" and time after
+//! checks during a maintenance window is excluded, "degraded" counts as available. An outage
+//! begins with a check reporting "not_available" outside of a maintenance window and ends with
+//! the next check reporting "available" or "degraded".
+//!
+//! # Example
+//! ```no_run
+//! # use std::time::{Duration, SystemTime};
+//! # use reachable::*;
+//! # use reachable::history::HistoryStore;
+//! let history = HistoryStore::open("history.log").unwrap();
+//! let mut exec = AsyncTargetExecutor::new();
+//! exec.add_observer(history);
+//!
+//! // Later: Uptime of the last 30 days.
+//! let history = HistoryStore::open("history.log").unwrap();
+//! let to = SystemTime::now();
+//! let from = to - Duration::from_secs(30 * 24 * 60 * 60);
+//! if let Some(uptime) = history.uptime("www.google.de:80", from, to).unwrap() {
+//!     println!("www.google.de:80 was {:.2}% available", uptime);
+//! }
+//! ```
+//!
+//! # Notes
+//! Requires crate to be configured with feature "history".
+
+use super::async_target::{CheckObserver, CheckReport};
+use super::{HistoryError, Status};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Documentation imports
+#[cfg(doc)]
+use super::AsyncTargetExecutor;
+
+/// Outage of a target, as reported by [HistoryStore::outages].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outage {
+    /// Point in time of the first check reporting the target as not available.
+    pub start: SystemTime,
+    /// Point in time of the first check reporting the target as available again.
+    /// [None] if the outage is ongoing.
+    pub end: Option<SystemTime>,
+}
+
+impl Outage {
+    /// Get the duration of this outage, [None] if the outage is ongoing.
+    pub fn duration(&self) -> Option<Duration> {
+        self.end.map(|end| end.duration_since(self.start).unwrap_or_default())
+    }
+}
+
+/// Recorded check of a single target.
+#[derive(Debug, PartialEq)]
+struct Check {
+    /// Point in time of the check in ms since the unix epoch.
+    timestamp: u64,
+    /// Recorded status value.
+    status: String,
+    /// True, if the check happened during a maintenance window.
+    maintenance: bool,
+}
+
+/// Persistent history of all checks, stored in an append-only file.
+pub struct HistoryStore {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl HistoryStore {
+    /// Open the history stored in given file. The file is created if it does not exist.
+    ///
+    /// # Errors
+    /// Returns a [HistoryError] if the file could not be opened.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, HistoryError> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path.as_ref())
+            .map_err(|error| HistoryError::from(("Failed to open history", error)))?;
+
+        // Note: Terminate a record cut short. Otherwise, the next record would be appended to it.
+        let mut last = [0; 1];
+        if file.seek(SeekFrom::End(-1)).is_ok() && file.read_exact(&mut last).is_ok() && last[0] != b'\n' {
+            file.write_all(b"\n")
+                .map_err(|error| HistoryError::from(("Failed to open history", error)))?;
+        }
+
+        Ok(HistoryStore {
+            path: path.as_ref().to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Get the percentage of time the target with given id was available within given time range.
+    /// The status of the last check lasts until now, time in the future is not covered.
+    ///
+    /// # Returns
+    /// The percentage, [None] if the status of the target is unknown for the whole range.
+    ///
+    /// # Errors
+    /// Returns a [HistoryError] if the history could not be read.
+    pub fn uptime(&self, id: &str, from: SystemTime, to: SystemTime) -> Result<Option<f64>, HistoryError> {
+        let (from, to) = (unix_millis(from), unix_millis(to));
+        let checks = self.checks(id)?;
+
+        let now = unix_millis(SystemTime::now());
+        let (mut up, mut down) = (0, 0);
+        for (index, check) in checks.iter().enumerate() {
+            let start = check.timestamp.clamp(from, to);
+            let end = checks.get(index + 1).map_or(now, |next| next.timestamp).clamp(from, to);
+            match (check.status.as_str(), check.maintenance) {
+                (_, true) => (),
+                ("available" | "degraded", false) => up += end.saturating_sub(start),
+                ("not_available", false) => down += end.saturating_sub(start),
+                _ => (),
+            }
+        }
+
+        Ok(match up + down {
+            0 => None,
+            total => Some(up as f64 * 100.0 / total as f64),
+        })
+    }
+
+    /// Get all outages of the target with given id, overlapping given time range.
+    ///
+    /// # Errors
+    /// Returns a [HistoryError] if the history could not be read.
+    pub fn outages(&self, id: &str, from: SystemTime, to: SystemTime) -> Result<Vec<Outage>, HistoryError> {
+        let (from, to) = (unix_millis(from), unix_millis(to));
+        let checks = self.checks(id)?;
+
+        let mut outages: Vec<(u64, Option<u64>)> = Vec::new();
+        let mut start = None;
+        for check in checks.iter() {
+            match (check.status.as_str(), start) {
+                ("not_available", None) if !check.maintenance => start = Some(check.timestamp),
+                ("available" | "degraded", Some(begin)) => {
+                    outages.push((begin, Some(check.timestamp)));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        if let Some(begin) = start {
+            outages.push((begin, None));
+        }
+
+        Ok(outages
+            .into_iter()
+            .filter(|(start, end)| *start < to && end.map(|end| end > from).unwrap_or(true))
+            .map(|(start, end)| Outage {
+                start: from_unix_millis(start),
+                end: end.map(from_unix_millis),
+            })
+            .collect())
+    }
+
+    /// Get the mean time to recovery of the target with given id, covering all outages
+    /// ended within given time range.
+    ///
+    /// # Returns
+    /// The mean duration of all covered outages, [None] if no outage ended within the range.
+    ///
+    /// # Errors
+    /// Returns a [HistoryError] if the history could not be read.
+    pub fn mttr(&self, id: &str, from: SystemTime, to: SystemTime) -> Result<Option<Duration>, HistoryError> {
+        let durations: Vec<Duration> = self
+            .outages(id, from, to)?
+            .iter()
+            .filter(|outage| outage.end.map(|end| from <= end && end <= to).unwrap_or(false))
+            .filter_map(Outage::duration)
+            .collect();
+
+        Ok(match durations.len() {
+            0 => None,
+            count => Some(durations.iter().sum::<Duration>() / count as u32),
+        })
+    }
+
+    /// Read all recorded checks of the target with given id, ordered by time.
+    fn checks(&self, id: &str) -> Result<Vec<Check>, HistoryError> {
+        let file = File::open(&self.path).map_err(|error| HistoryError::from(("Failed to read history", error)))?;
+
+        let mut checks = Vec::new();
+        let mut reader = BufReader::new(file);
+        let mut bytes = Vec::new();
+        for index in 0.. {
+            bytes.clear();
+            if reader.read_until(b'\n', &mut bytes)? == 0 {
+                break;
+            }
+            let line = match std::str::from_utf8(&bytes) {
+                Ok(line) => line.trim_end_matches('\n').trim_end_matches('\r'),
+                // Note: Records are written as UTF-8. A record cut within a multi-byte character,
+                // e.g. by a crash while writing, is skipped.
+                Err(_) => continue,
+            };
+            let fields: Vec<&str> = line.split('\t').collect();
+            let timestamp = match fields.first().map(|field| field.parse::<u64>()) {
+                Some(Ok(timestamp)) if fields.len() == 5 => timestamp,
+                // Note: A record cut short, e.g. by a crash while writing, is skipped.
+                Some(Ok(_)) if fields.len() < 5 => continue,
+                _ => return Err(HistoryError::InvalidRecord(index + 1)),
+            };
+
+            if (fields[1] == "check" || fields[1] == "maintenance") && unescape(fields[2]) == id {
+                checks.push(Check {
+                    timestamp,
+                    status: String::from(fields[3]),
+                    maintenance: fields[1] == "maintenance",
+                });
+            }
+        }
+
+        // Note: Concurrent checks may finish in a different order than they were written.
+        checks.sort_by_key(|check| check.timestamp);
+        Ok(checks)
+    }
+
+    /// Append given lines to the history file.
+    fn append(&self, lines: &str) -> Result<(), HistoryError> {
+        let mut file = self.file.lock().unwrap();
+        file.write_all(lines.as_bytes())?;
+        Ok(file.flush()?)
+    }
+}
+
+impl CheckObserver for HistoryStore {
+    fn on_check(&self, report: &CheckReport) {
+        let timestamp = unix_millis(report.timestamp);
+        let id = escape(report.id);
+        let detail = match (report.status, report.error) {
+            (_, Some(error)) => escape(&error.to_string()),
+            (Status::Degraded(reason), None) => escape(reason),
+            _ => String::new(),
+        };
+
+        let mut lines = format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            timestamp,
+            if report.maintenance { "maintenance" } else { "check" },
+            id,
+            status_value(report.status),
+            detail
+        );
+        if !report.status.same_state(report.old_status) {
+            lines.push_str(&format!(
+                "{}\ttransition\t{}\t{}\t{}\n",
+                timestamp,
+                id,
+                status_value(report.status),
+                status_value(report.old_status)
+            ));
+        }
+
+        // Note: A failing history must not disturb the checks. Errors are ignored.
+        let _ = self.append(&lines);
+    }
+}
+
+/// Get the recorded value of given status.
+fn status_value(status: &Status) -> &'static str {
+    match status {
+        Status::Unknown => "unknown",
+        Status::Available => "available",
+        Status::Degraded(_) => "degraded",
+        Status::NotAvailable => "not_available",
+    }
+}
+
+/// Get the milliseconds since the unix epoch of given point in time.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// Get the point in time of given milliseconds since the unix epoch.
+fn from_unix_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+/// Escape tabs, line breaks and backslashes of given field.
+fn escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Revert [escape].
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(character) = chars.next() {
+        match (character, character == '\\') {
+            (_, true) => match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some('r') => unescaped.push('\r'),
+                Some(other) => unescaped.push(other),
+                None => unescaped.push('\\'),
+            },
+            (character, false) => unescaped.push(character),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::CheckTargetError;
+
+    /// Create an empty history file in the temporary directory.
+    fn history(name: &str) -> (HistoryStore, PathBuf) {
+        let path = std::env::temp_dir().join(format!("reachable-{}-{}.log", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        (HistoryStore::open(&path).unwrap(), path)
+    }
+
+    fn time(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    /// Record a check of target "a" for each given pair of time and status.
+    fn record(history: &HistoryStore, checks: &[(u64, Status)]) {
+        record_maintenance(history, checks, false)
+    }
+
+    /// Record a check of target "a" for each given pair of time and status, optionally during a
+    /// maintenance window.
+    fn record_maintenance(history: &HistoryStore, checks: &[(u64, Status)], maintenance: bool) {
+        let mut old_status = Status::Unknown;
+        for (secs, status) in checks.iter() {
+            let mut report = CheckReport::new("a", status, &old_status, None, Duration::from_millis(10), time(*secs));
+            report.maintenance = maintenance;
+            history.on_check(&report);
+            old_status = status.clone();
+        }
+    }
+
+    #[test]
+    fn history_store_records() {
+        let (history, path) = history("records");
+        let error = CheckTargetError::from("Error\tin line");
+        history.on_check(&CheckReport::new(
+            "a\tb",
+            &Status::Unknown,
+            &Status::Available,
+            Some(&error),
+            Duration::from_millis(10),
+            time(1),
+        ));
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "1000\tcheck\ta\\tb\tunknown\tError\\tin line\n1000\ttransition\ta\\tb\tunknown\tavailable\n"
+        );
+        assert_eq!(unescape("a\\tb\\\\c"), "a\tb\\c");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn history_store_uptime() {
+        // Expectency: The time between two checks is attributed to the earlier check, unknown
+        //             time is excluded.
+        let (history, path) = history("uptime");
+        record(
+            &history,
+            &[
+                (0, Status::Available),
+                (60, Status::NotAvailable),
+                (90, Status::Degraded(String::from("slow"))),
+                (100, Status::Unknown),
+                (200, Status::Available),
+            ],
+        );
+
+        // Available 0-60 and 90-100, not available 60-90, unknown 100-200
+        assert_eq!(history.uptime("a", time(0), time(200)).unwrap(), Some(70.0));
+        assert_eq!(history.uptime("a", time(60), time(70)).unwrap(), Some(0.0));
+        assert_eq!(history.uptime("a", time(120), time(200)).unwrap(), None);
+        assert_eq!(history.uptime("b", time(0), time(200)).unwrap(), None);
+
+        // The status of the last check lasts until now, e.g. available 200-300, not in the future
+        assert_eq!(history.uptime("a", time(100), time(300)).unwrap(), Some(100.0));
+        assert_eq!(history.uptime("a", time(0), time(300)).unwrap(), Some(85.0));
+        let future = SystemTime::now() + Duration::from_secs(3600);
+        assert_eq!(
+            history
+                .uptime("a", SystemTime::now() - Duration::from_secs(1), future)
+                .unwrap(),
+            Some(100.0)
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn history_store_maintenance() {
+        // Expectency: Checks during maintenance windows are recorded as such. They are excluded
+        //             from the uptime and do not begin outages.
+        let (history, path) = history("maintenance");
+        record(&history, &[(0, Status::Available)]);
+        record_maintenance(&history, &[(60, Status::NotAvailable), (70, Status::Available)], true);
+        record(&history, &[(80, Status::NotAvailable), (90, Status::Available)]);
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("60000\tmaintenance\ta\tnot_available\t\n"));
+
+        // Available 0-60 and 90-100, maintenance 60-80, not available 80-90
+        assert_eq!(history.uptime("a", time(0), time(100)).unwrap(), Some(87.5));
+        assert_eq!(history.uptime("a", time(60), time(80)).unwrap(), None);
+        assert_eq!(
+            history.outages("a", time(0), time(100)).unwrap(),
+            vec![Outage {
+                start: time(80),
+                end: Some(time(90))
+            }]
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn history_store_outages_and_mttr() {
+        let (history, path) = history("outages");
+        record(
+            &history,
+            &[
+                (0, Status::Available),
+                (10, Status::NotAvailable),
+                (20, Status::Unknown),
+                (30, Status::Available),
+                (100, Status::NotAvailable),
+                (110, Status::Available),
+                (200, Status::NotAvailable),
+            ],
+        );
+
+        let outages = history.outages("a", time(0), time(300)).unwrap();
+        assert_eq!(
+            outages,
+            vec![
+                Outage {
+                    start: time(10),
+                    end: Some(time(30))
+                },
+                Outage {
+                    start: time(100),
+                    end: Some(time(110))
+                },
+                Outage {
+                    start: time(200),
+                    end: None
+                },
+            ]
+        );
+        assert_eq!(history.outages("a", time(40), time(90)).unwrap(), vec![]);
+        assert_eq!(
+            history.mttr("a", time(0), time(300)).unwrap(),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(
+            history.mttr("a", time(50), time(300)).unwrap(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(history.mttr("a", time(150), time(300)).unwrap(), None);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn history_store_invalid_record() {
+        let (history, path) = history("invalid");
+        record(&history, &[(0, Status::Available)]);
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"garbage\n")
+            .unwrap();
+
+        let error = history.uptime("a", time(0), time(10)).unwrap_err();
+        assert_eq!(format!("{}", error), "Invalid record in line 3");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn history_store_truncated_record() {
+        // Expectency: A record cut short by a crash is skipped. After reopening, new records are
+        //             appended on a new line.
+        let (history, path) = history("truncated");
+        record(&history, &[(0, Status::Available), (10, Status::NotAvailable)]);
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"20000\tcheck\ta\tnot_av")
+            .unwrap();
+        assert_eq!(history.uptime("a", time(0), time(10)).unwrap(), Some(100.0));
+
+        drop(history);
+        let history = HistoryStore::open(&path).unwrap();
+        history.on_check(&CheckReport::new(
+            "a",
+            &Status::Available,
+            &Status::NotAvailable,
+            None,
+            Duration::from_millis(10),
+            time(20),
+        ));
+        assert_eq!(history.uptime("a", time(0), time(20)).unwrap(), Some(50.0));
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("not_av\n20000\tcheck\ta\tavailable\t\n"));
+
+        // Cut within the multi-byte character of an id, first as last record, then after reopening
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&"30000\tcheck\tä".as_bytes()[..13])
+            .unwrap();
+        assert_eq!(history.uptime("a", time(0), time(20)).unwrap(), Some(50.0));
+        drop(history);
+        let history = HistoryStore::open(&path).unwrap();
+        record(&history, &[(40, Status::NotAvailable)]);
+        assert_eq!(history.uptime("a", time(0), time(40)).unwrap(), Some(75.0));
+        fs::remove_file(path).unwrap();
+    }
+}
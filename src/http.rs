@@ -0,0 +1,537 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains a minimal HTTP/1.1 server used by the optional HTTP based exporters.
+//!
+//! # Notes
+//! The server is deliberately simple: Requests are handled one after another on a dedicated
+//! thread, only the request line is evaluated and each connection is closed after the response.
+//! The webhook notifier only requires the client side of this module.
+#![cfg_attr(not(any(feature = "prometheus", feature = "status_api")), allow(dead_code))]
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Timeout applied on reading a whole request and on writing responses.
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum accepted length of a request body in bytes. Larger requests are rejected.
+const MAX_BODY_LENGTH: usize = 64 * 1024;
+
+/// Maximum accepted length of the request line and of each header line in bytes.
+const MAX_LINE_LENGTH: usize = 8 * 1024;
+
+/// Maximum accepted number of headers of a request.
+const MAX_HEADERS: usize = 100;
+
+/// Interval the server polls for new connections and for its shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Type containing a boxed trait object, answering a [Request] with a [Response].
+pub(crate) type BoxedRoutes = Box<dyn Fn(&Request) -> Response + Send + 'static>;
+
+/// Received HTTP request.
+#[derive(Debug)]
+pub(crate) struct Request {
+    /// Request method, e.g. "GET".
+    pub method: String,
+    /// Requested path without query string.
+    pub path: String,
+    /// Request body, read according to the Content-Length header.
+    pub body: String,
+}
+
+/// HTTP response to send.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Response {
+    /// Status code of the response.
+    pub status: u16,
+    /// Value of the Content-Type header.
+    pub content_type: &'static str,
+    /// Response body.
+    pub body: String,
+}
+
+impl Response {
+    /// Construct a response with status code 200.
+    pub fn ok(content_type: &'static str, body: String) -> Self {
+        Response {
+            status: 200,
+            content_type,
+            body,
+        }
+    }
+
+    /// Construct a plain text response with given error status code.
+    pub fn error(status: u16) -> Self {
+        Response {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: format!("{}\n", reason(status)),
+        }
+    }
+}
+
+/// Running HTTP server. The server is stopped on drop.
+pub(crate) struct HttpServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HttpServer {
+    /// Bind a listener to given address and serve all requests with given routes.
+    ///
+    /// # Errors
+    /// Returns an [io::Error] if the listener could not be bound.
+    pub fn bind<A: ToSocketAddrs>(addr: A, routes: BoxedRoutes) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        // Note: The listener is polled, the shutdown must not depend on a connection to it.
+        listener.set_nonblocking(true)?;
+
+        let stop = shutdown.clone();
+        let thread = thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        // Note: Failures while handling a single connection must not stop the server.
+                        let _ = stream
+                            .set_nonblocking(false)
+                            .and_then(|_| handle_connection(stream, &routes));
+                    }
+                    Err(_) => thread::sleep(POLL_INTERVAL),
+                }
+            }
+        });
+
+        Ok(HttpServer {
+            addr,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Get the address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for HttpServer {
+    fn drop(&mut self) {
+        // Signal shutdown, the server thread notices it on its next poll.
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Reader of a connection, failing all reads after a deadline for the whole request.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::from(io::ErrorKind::TimedOut));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Read a single request from given stream and write the response generated by given routes.
+fn handle_connection(stream: TcpStream, routes: &BoxedRoutes) -> io::Result<()> {
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    // Parse request line, evaluate the Content-Length header and read the body
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + IO_TIMEOUT,
+    });
+    let request = read_line(&mut reader)?.and_then(|line| parse_request_line(&line));
+    let response = match request {
+        Some(mut request) => {
+            // Note: The length is either valid or the status code of the rejection.
+            let mut length = Ok(0);
+            let mut headers = 0;
+            loop {
+                let header = match read_line(&mut reader)? {
+                    Some(header) => header,
+                    None => break length = Err(431),
+                };
+                if header.trim().is_empty() {
+                    break;
+                }
+                headers += 1;
+                if headers > MAX_HEADERS {
+                    break length = Err(431);
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.trim().eq_ignore_ascii_case("content-length") {
+                        length = match value.trim().parse::<usize>() {
+                            Ok(length) if length <= MAX_BODY_LENGTH => Ok(length),
+                            Ok(_) => Err(413),
+                            Err(_) => Err(400),
+                        };
+                    }
+                }
+            }
+
+            match length {
+                Ok(length) => {
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body)?;
+                    request.body = String::from_utf8_lossy(&body).into_owned();
+                    routes(&request)
+                }
+                Err(status) => Response::error(status),
+            }
+        }
+        None => Response::error(400),
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Read a single line of at most [MAX_LINE_LENGTH] bytes. Returns [None], if the line is longer.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE_LENGTH as u64).read_line(&mut line)?;
+    match line.len() >= MAX_LINE_LENGTH && !line.ends_with('\n') {
+        true => Ok(None),
+        false => Ok(Some(line)),
+    }
+}
+
+/// Parse a request line like "GET /metrics HTTP/1.1".
+fn parse_request_line(line: &str) -> Option<Request> {
+    let mut pieces = line.split_whitespace();
+    let method = pieces.next()?;
+    let target = pieces.next()?;
+    if !pieces.next()?.starts_with("HTTP/") || !target.starts_with('/') {
+        return None;
+    }
+
+    let path = target.split('?').next().unwrap_or(target);
+    Some(Request {
+        method: String::from(method),
+        path: String::from(path),
+        body: String::new(),
+    })
+}
+
+/// Decode a percent encoded path segment. Returns [None] on invalid encodings.
+#[cfg(feature = "status_api")]
+pub(crate) fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut iter = segment.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = (iter.next()? as char).to_digit(16)?;
+            let low = (iter.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// URL of a resource reachable via plain HTTP.
+#[cfg(feature = "webhook")]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HttpUrl {
+    /// Host name or address of the server.
+    pub host: String,
+    /// Port of the server.
+    pub port: u16,
+    /// Path of the resource, including the query string.
+    pub path: String,
+}
+
+#[cfg(feature = "webhook")]
+impl HttpUrl {
+    /// Parse an URL like "http://host[:port][/path]". Returns [None] for invalid or non HTTP URLs.
+    pub fn parse(url: &str) -> Option<HttpUrl> {
+        let rest = url.strip_prefix("http://")?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !authority.ends_with(']') => (host, port.parse().ok().filter(|port| *port != 0)?),
+            _ => (authority, 80),
+        };
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(HttpUrl {
+            host: String::from(host.trim_start_matches('[').trim_end_matches(']')),
+            port,
+            path: String::from(path),
+        })
+    }
+}
+
+/// Send a POST request with given body to given URL.
+///
+/// # Returns
+/// The status code of the response.
+#[cfg(feature = "webhook")]
+pub(crate) fn post(url: &HttpUrl, content_type: &str, body: &str, timeout: Duration) -> io::Result<u16> {
+    let addr = (url.host.as_str(), url.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Host could not be resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        url.path,
+        url.host,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+
+    // Evaluate the status line only
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid HTTP response"))
+}
+
+/// Get the reason phrase of given status code.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    /// Send a GET request for given path to given address and return status code and body.
+    pub(crate) fn get(addr: SocketAddr, path: &str) -> (u16, String) {
+        send(addr, &format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path))
+    }
+
+    /// Send given raw request to given address and return status code and body.
+    fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "{}", request).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, String::from(body))
+    }
+
+    #[test]
+    fn http_parse_request_line() {
+        // Expectency: Method and path (without query) are parsed from the request line, malformed
+        //             request lines are rejected.
+        let request = parse_request_line("GET /metrics?x=1 HTTP/1.1\r\n").unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/metrics");
+
+        assert!(parse_request_line("").is_none());
+        assert!(parse_request_line("GET\r\n").is_none());
+        assert!(parse_request_line("GET metrics HTTP/1.1\r\n").is_none());
+        assert!(parse_request_line("GET /metrics FTP\r\n").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "status_api")]
+    fn http_percent_decode() {
+        // Expectency: Percent encoded characters are decoded, incomplete or invalid encodings are
+        //             rejected.
+        assert_eq!(percent_decode("host%3A80"), Some(String::from("host:80")));
+        assert_eq!(percent_decode("plain"), Some(String::from("plain")));
+        assert_eq!(percent_decode("%3"), None);
+        assert_eq!(percent_decode("%zz"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "webhook")]
+    fn http_url_parse() {
+        // Expectency: Host, port (default 80) and path are parsed from http URLs, other schemes and
+        //             invalid hosts or ports are rejected.
+        let url = |host: &str, port, path: &str| {
+            Some(HttpUrl {
+                host: String::from(host),
+                port,
+                path: String::from(path),
+            })
+        };
+        assert_eq!(HttpUrl::parse("http://localhost"), url("localhost", 80, "/"));
+        assert_eq!(
+            HttpUrl::parse("http://127.0.0.1:8080/hook?x=1"),
+            url("127.0.0.1", 8080, "/hook?x=1")
+        );
+        assert_eq!(HttpUrl::parse("http://[::1]:8080/hook"), url("::1", 8080, "/hook"));
+
+        assert!(HttpUrl::parse("https://localhost").is_none());
+        assert!(HttpUrl::parse("http://:80/").is_none());
+        assert!(HttpUrl::parse("http://localhost:0/").is_none());
+        assert!(HttpUrl::parse("http://localhost:port/").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "webhook")]
+    fn http_post() {
+        // Expectency: The body is posted to the URL and the HTTP status of the response is returned.
+        let (send, recv) = std::sync::mpsc::channel();
+        let send = std::sync::Mutex::new(send);
+        let routes: BoxedRoutes = Box::new(move |request: &Request| {
+            send.lock().unwrap().send(request.body.clone()).unwrap();
+            Response::error(503)
+        });
+        let server = HttpServer::bind("127.0.0.1:0", routes).unwrap();
+
+        let url = HttpUrl::parse(&format!("http://{}/hook", server.local_addr())).unwrap();
+        assert_eq!(post(&url, "application/json", "{}", IO_TIMEOUT).unwrap(), 503);
+        assert_eq!(recv.recv().unwrap(), "{}");
+    }
+
+    #[test]
+    fn http_server_serve() {
+        // Expectency: Requests are answered by the routes, unknown paths with 404.
+        let routes: BoxedRoutes = Box::new(|request: &Request| match request.path.as_str() {
+            "/" => Response::ok("text/plain", String::from("hello")),
+            _ => Response::error(404),
+        });
+        let server = HttpServer::bind("127.0.0.1:0", routes).unwrap();
+
+        assert_eq!(get(server.local_addr(), "/"), (200, String::from("hello")));
+        assert_eq!(get(server.local_addr(), "/missing"), (404, String::from("Not Found\n")));
+    }
+
+    #[test]
+    fn http_server_drop_wildcard() {
+        // Expectency: A server bound to a wildcard address stops on drop without waiting for a
+        //             connection.
+        let routes: BoxedRoutes = Box::new(|_: &Request| Response::error(404));
+        let server = HttpServer::bind("0.0.0.0:0", routes).unwrap();
+        let start = Instant::now();
+        drop(server);
+        assert!(start.elapsed() < IO_TIMEOUT);
+    }
+
+    #[test]
+    fn http_server_reject_content_length() {
+        // Expectency: Requests with an oversized or invalid Content-Length are rejected without
+        //             reading the body or calling the routes.
+        let routes: BoxedRoutes = Box::new(|request: &Request| Response::ok("text/plain", request.body.clone()));
+        let server = HttpServer::bind("127.0.0.1:0", routes).unwrap();
+        let post = |length: &str| {
+            send(
+                server.local_addr(),
+                &format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\nbody", length),
+            )
+        };
+
+        assert_eq!(post("4"), (200, String::from("body")));
+        assert_eq!(post("999999999999"), (413, String::from("Payload Too Large\n")));
+        assert_eq!(
+            post(&(MAX_BODY_LENGTH + 1).to_string()),
+            (413, String::from("Payload Too Large\n"))
+        );
+        assert_eq!(post("four"), (400, String::from("Bad Request\n")));
+        assert_eq!(post("-1"), (400, String::from("Bad Request\n")));
+    }
+
+    #[test]
+    fn http_deadline_reader() {
+        // Expectency: Reads fail once the deadline of the whole request passed, even if data
+        //             keeps arriving.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        client.write_all(b"GET").unwrap();
+
+        let mut buf = [0; 3];
+        let mut reader = DeadlineReader {
+            stream: &stream,
+            deadline: Instant::now() + IO_TIMEOUT,
+        };
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        reader.deadline = Instant::now();
+        client.write_all(b"GET").unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap_err().kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn http_server_reject_long_headers() {
+        // Expectency: Overlong request lines, overlong header lines and too many headers are
+        //             rejected. Short header lines do not end the headers.
+        let routes: BoxedRoutes = Box::new(|request: &Request| Response::ok("text/plain", request.body.clone()));
+        let server = HttpServer::bind("127.0.0.1:0", routes).unwrap();
+        let addr = server.local_addr();
+
+        assert_eq!(
+            send(addr, "POST / HTTP/1.1\r\na\nContent-Length: 4\r\n\r\nbody"),
+            (200, String::from("body"))
+        );
+        let long = "x".repeat(MAX_LINE_LENGTH);
+        assert_eq!(
+            send(addr, &format!("GET /{}", long)[..MAX_LINE_LENGTH]),
+            (400, String::from("Bad Request\n"))
+        );
+        assert_eq!(
+            send(addr, &format!("GET / HTTP/1.1\r\nX: {}", long)[..16 + MAX_LINE_LENGTH]),
+            (431, String::from("Request Header Fields Too Large\n"))
+        );
+        assert_eq!(
+            send(
+                addr,
+                &format!("GET / HTTP/1.1\r\n{}", "X: y\r\n".repeat(MAX_HEADERS + 1))
+            ),
+            (431, String::from("Request Header Fields Too Large\n"))
+        );
+    }
+}
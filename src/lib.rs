@@ -11,20 +11,68 @@
 //! usable to check, if a computer is available over the network.
 //!
 //! Additionally this crate contains asynchronous utilities to execute these checks regularly
-//! within a given time interval.
+//! within a given time interval or at fixed times. Wall-clock based check schedules and maintenance
+//! windows, silencing expected downtimes, are described with the "schedule" module.
+//!
+//! Checks can be run as Nagios/Icinga compatible plugin with the "nagios" module.
+//!
+//! Whole monitoring setups can be described declaratively in TOML or YAML files and loaded with
+//! the "config" module (requires feature "config").
+//!
+//! The checks of an executor can be exported as Prometheus metrics with the "prometheus" module
+//! (requires feature "prometheus") or inspected via an embedded HTTP status API with the
+//! "status_api" module (requires feature "status_api"). Status transitions can be posted to a
+//! webhook with the "webhook" module (requires feature "webhook"). A persistent check history
+//! offering uptime reports is provided by the "history" module (requires feature "history").
 
 // Modules
 pub mod error;
+pub mod nagios;
 pub mod resolve_policy;
+pub mod schedule;
 pub mod target;
+mod time;
 
 #[cfg(feature = "async")]
 pub mod async_target;
 
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(any(feature = "prometheus", feature = "status_api", feature = "webhook"))]
+mod http;
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
+#[cfg(feature = "status_api")]
+pub mod status_api;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+#[cfg(feature = "history")]
+pub mod history;
+
 // Re-exports
-pub use error::{CheckTargetError, ParseTargetError, ResolveTargetError};
+pub use error::{CheckTargetError, ParseScheduleError, ParseTargetError, ResolveTargetError};
 pub use resolve_policy::ResolvePolicy;
-pub use target::{Fqhn, IcmpTarget, Port, Status, Target, TcpTarget};
+pub use target::{CompositeTarget, Fqhn, IcmpStatistics, IcmpTarget, Port, Status, Target, TcpTarget};
+
+#[cfg(feature = "async")]
+pub use async_target::{
+    AsyncTarget, AsyncTargetExecutor, BoxedHandler, BoxedTarget, CheckObserver, CheckReport, DurationHistogram,
+    ExecutorMetrics, FlappingPolicy, IntervalPolicy, OldStatus, SharedObserver, TargetMetrics,
+};
 
 #[cfg(feature = "async")]
-pub use async_target::{AsyncTarget, AsyncTargetExecutor, BoxedHandler, BoxedTarget, OldStatus};
+pub use error::DependencyError;
+
+#[cfg(feature = "config")]
+pub use error::ConfigError;
+
+#[cfg(feature = "webhook")]
+pub use error::WebhookError;
+
+#[cfg(feature = "history")]
+pub use error::HistoryError;
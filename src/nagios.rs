@@ -0,0 +1,280 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains utilities to use [Target] checks as Nagios/Icinga compatible plugin.
+//!
+//! A plugin prints a single status line, optionally followed by performance data, and
+//! reports its result via exit code:
+//!
+//! | Result                                        | State    | Exit code |
+//! |-----------------------------------------------|----------|-----------|
+//! | [Status::Available]                           | OK       | 0         |
+//! | [Status::Degraded] or latency above warning   | WARNING  | 1         |
+//! | [Status::NotAvailable] or latency above critical | CRITICAL | 2      |
+//! | [Status::Unknown] or [CheckTargetError]       | UNKNOWN  | 3         |
+//!
+//! The latency is the duration of [Target::check_availability]. It is reported as
+//! performance data "rtt" in milliseconds, including the configured thresholds.
+//!
+//! # Example
+//! ```
+//! # use std::str::FromStr;
+//! # use std::time::Duration;
+//! # use reachable::TcpTarget;
+//! # use reachable::nagios::{NagiosCheck, NagiosState};
+//! let target = TcpTarget::from_str("127.0.0.1:1").unwrap();
+//! let result = NagiosCheck::new()
+//!     .set_warning(Duration::from_millis(100))
+//!     .set_critical(Duration::from_millis(500))
+//!     .check(&target);
+//!
+//! // Prints e.g. "REACHABLE CRITICAL - 127.0.0.1:1 is not available | rtt=0ms;100;500;0"
+//! println!("{}", result);
+//! assert_eq!(result.get_state(), NagiosState::Critical);
+//! assert_eq!(result.get_state().exit_code(), 2);
+//! ```
+
+use super::{CheckTargetError, Status, Target};
+use std::fmt::{self};
+use std::time::{Duration, Instant};
+
+/// State of a Nagios plugin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NagiosState {
+    /// Target is available within the thresholds.
+    Ok,
+    /// Target is degraded or exceeded the warning threshold.
+    Warning,
+    /// Target is not available or exceeded the critical threshold.
+    Critical,
+    /// Target could not be checked.
+    Unknown,
+}
+
+impl NagiosState {
+    /// Get the plugin exit code of this state.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            NagiosState::Ok => 0,
+            NagiosState::Warning => 1,
+            NagiosState::Critical => 2,
+            NagiosState::Unknown => 3,
+        }
+    }
+}
+
+impl fmt::Display for NagiosState {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NagiosState::Ok => write!(formatter, "OK"),
+            NagiosState::Warning => write!(formatter, "WARNING"),
+            NagiosState::Critical => write!(formatter, "CRITICAL"),
+            NagiosState::Unknown => write!(formatter, "UNKNOWN"),
+        }
+    }
+}
+
+/// Result of a [NagiosCheck]. Display prints the plugin output line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NagiosResult {
+    state: NagiosState,
+    message: String,
+    perfdata: Option<String>,
+}
+
+impl NagiosResult {
+    /// Get the [NagiosState].
+    pub fn get_state(&self) -> NagiosState {
+        self.state
+    }
+
+    /// Get the human readable message.
+    pub fn get_message(&self) -> &String {
+        &self.message
+    }
+
+    /// Get the performance data, if the check finished without error.
+    pub fn get_perfdata(&self) -> Option<&String> {
+        self.perfdata.as_ref()
+    }
+}
+
+impl fmt::Display for NagiosResult {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "REACHABLE {} - {}", self.state, self.message)?;
+        match &self.perfdata {
+            Some(perfdata) => write!(formatter, " | {}", perfdata),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Nagios/Icinga compatible check of a [Target] with optional latency thresholds.
+#[derive(Debug, Default)]
+pub struct NagiosCheck {
+    warning: Option<Duration>,
+    critical: Option<Duration>,
+}
+
+impl NagiosCheck {
+    /// Construct a [NagiosCheck] without latency thresholds.
+    pub fn new() -> Self {
+        NagiosCheck::default()
+    }
+
+    /// Set the latency, above which a reachable target is reported as WARNING.
+    pub fn set_warning(mut self, warning: Duration) -> Self {
+        self.warning = Some(warning);
+        self
+    }
+
+    /// Set the latency, above which a reachable target is reported as CRITICAL.
+    pub fn set_critical(mut self, critical: Duration) -> Self {
+        self.critical = Some(critical);
+        self
+    }
+
+    /// Get the warning latency threshold.
+    pub fn get_warning(&self) -> Option<&Duration> {
+        self.warning.as_ref()
+    }
+
+    /// Get the critical latency threshold.
+    pub fn get_critical(&self) -> Option<&Duration> {
+        self.critical.as_ref()
+    }
+
+    /// Check the availability of given target and evaluate the result.
+    pub fn check(&self, target: &dyn Target) -> NagiosResult {
+        let started = Instant::now();
+        let result = target.check_availability();
+        self.evaluate(&target.get_id(), result, started.elapsed())
+    }
+
+    /// Evaluate the result of an availability check of the target with given id.
+    ///
+    /// # Arguments
+    /// * id: identifier of the checked target.
+    /// * result: result of [Target::check_availability].
+    /// * latency: duration of the availability check.
+    pub fn evaluate(&self, id: &str, result: Result<Status, CheckTargetError>, latency: Duration) -> NagiosResult {
+        let status = match result {
+            Ok(status) => status,
+            Err(error) => {
+                return NagiosResult {
+                    state: NagiosState::Unknown,
+                    message: format!("{} could not be checked: {}", id, error),
+                    perfdata: None,
+                }
+            }
+        };
+
+        let exceeds = |threshold: Option<Duration>| threshold.map(|threshold| latency > threshold).unwrap_or(false);
+        let (state, mut message) = match &status {
+            Status::Available => (NagiosState::Ok, format!("{} is available", id)),
+            Status::Degraded(reason) => (NagiosState::Warning, format!("{} is degraded: {}", id, reason)),
+            Status::NotAvailable => (NagiosState::Critical, format!("{} is not available", id)),
+            Status::Unknown => (NagiosState::Unknown, format!("{} is in unknown state", id)),
+        };
+
+        // Thresholds can only worsen the state of reachable targets
+        let state = match state {
+            NagiosState::Ok | NagiosState::Warning if exceeds(self.critical) => NagiosState::Critical,
+            NagiosState::Ok if exceeds(self.warning) => NagiosState::Warning,
+            state => state,
+        };
+        if matches!(status, Status::Available | Status::Degraded(_)) {
+            message = format!("{} in {}ms", message, latency.as_millis());
+        }
+
+        let threshold = |threshold: Option<Duration>| threshold.map(|t| t.as_millis().to_string()).unwrap_or_default();
+        NagiosResult {
+            state,
+            message,
+            perfdata: Some(format!(
+                "rtt={}ms;{};{};0",
+                latency.as_millis(),
+                threshold(self.warning),
+                threshold(self.critical)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nagios_check_evaluate_status() {
+        // Expectency: Each Status maps onto its plugin state, errors are reported as UNKNOWN.
+        let check = NagiosCheck::new();
+        let latency = Duration::from_millis(12);
+        let expected = vec![
+            (
+                Ok(Status::Available),
+                NagiosState::Ok,
+                "REACHABLE OK - a is available in 12ms | rtt=12ms;;;0",
+            ),
+            (
+                Ok(Status::Degraded(String::from("slow"))),
+                NagiosState::Warning,
+                "REACHABLE WARNING - a is degraded: slow in 12ms | rtt=12ms;;;0",
+            ),
+            (
+                Ok(Status::NotAvailable),
+                NagiosState::Critical,
+                "REACHABLE CRITICAL - a is not available | rtt=12ms;;;0",
+            ),
+            (
+                Ok(Status::Unknown),
+                NagiosState::Unknown,
+                "REACHABLE UNKNOWN - a is in unknown state | rtt=12ms;;;0",
+            ),
+            (
+                Err(CheckTargetError::from("Error")),
+                NagiosState::Unknown,
+                "REACHABLE UNKNOWN - a could not be checked: Error",
+            ),
+        ];
+
+        for (result, state, output) in expected {
+            let result = check.evaluate("a", result, latency);
+            assert_eq!(result.get_state(), state);
+            assert_eq!(result.to_string(), output);
+        }
+    }
+
+    #[test]
+    fn nagios_check_evaluate_thresholds() {
+        // Expectency: Latency thresholds worsen the state of reachable targets only.
+        let check = NagiosCheck::new()
+            .set_warning(Duration::from_millis(100))
+            .set_critical(Duration::from_millis(500));
+        let evaluate = |status, millis| check.evaluate("a", Ok(status), Duration::from_millis(millis));
+
+        assert_eq!(evaluate(Status::Available, 100).get_state(), NagiosState::Ok);
+        assert_eq!(evaluate(Status::Available, 101).get_state(), NagiosState::Warning);
+        assert_eq!(evaluate(Status::Available, 501).get_state(), NagiosState::Critical);
+        assert_eq!(
+            evaluate(Status::Degraded(String::from("slow")), 501).get_state(),
+            NagiosState::Critical
+        );
+        assert_eq!(evaluate(Status::Unknown, 501).get_state(), NagiosState::Unknown);
+        assert_eq!(
+            evaluate(Status::Available, 101).get_perfdata(),
+            Some(&String::from("rtt=101ms;100;500;0"))
+        );
+    }
+
+    #[test]
+    fn nagios_state_exit_code() {
+        assert_eq!(NagiosState::Ok.exit_code(), 0);
+        assert_eq!(NagiosState::Warning.exit_code(), 1);
+        assert_eq!(NagiosState::Critical.exit_code(), 2);
+        assert_eq!(NagiosState::Unknown.exit_code(), 3);
+    }
+}
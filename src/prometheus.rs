@@ -0,0 +1,384 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains a Prometheus exporter for the checks of an [AsyncTargetExecutor].
+//!
+//! The exporter is a [CheckObserver] collecting the following metrics per target, labeled
+//! with the target id:
+//! * `reachable_up`: 1 if the target was available or degraded on the last check, 0 otherwise.
+//! * `reachable_check_duration_seconds`: Duration of the last successful check, including all
+//!   retries.
+//! * `reachable_checks_total`: Number of performed checks.
+//! * `reachable_failures_total`: Number of checks reporting the target as not available.
+//! * `reachable_errors_total`: Number of failed checks, additionally labeled by error kind.
+//! * `reachable_last_check_timestamp_seconds`: Unix timestamp of the last check.
+//! * `reachable_maintenance`: 1 if the last check happened during a maintenance window, 0 otherwise.
+//! * `reachable_parent_unreachable`: 1 if the target was unreachable due to a parent on the last
+//!   check, 0 otherwise.
+//! * `reachable_flapping`: 1 if the target is flapping, 0 otherwise.
+//! * `reachable_handler_panics_total`: Number of panics caught in the check handler.
+//!
+//! # Example
+//! ```no_run
+//! # use reachable::*;
+//! # use reachable::prometheus::PrometheusExporter;
+//! let exporter = PrometheusExporter::new();
+//! let mut exec = AsyncTargetExecutor::new();
+//! exec.add_observer(exporter.clone());
+//!
+//! // Serve metrics on http://127.0.0.1:9185/metrics until server is dropped.
+//! let server = exporter.serve("127.0.0.1:9185").unwrap();
+//! ```
+//!
+//! # Notes
+//! Requires crate to be configured with feature "prometheus".
+
+use super::async_target::{CheckObserver, CheckReport};
+use super::http::{BoxedRoutes, HttpServer, Request, Response};
+use super::{CheckTargetError, Status};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::{self};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+// Documentation imports
+#[cfg(doc)]
+use super::AsyncTargetExecutor;
+
+/// Content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Metrics collected for a single target.
+#[derive(Debug, Default)]
+struct TargetMetrics {
+    up: bool,
+    check_duration: Option<f64>,
+    checks: u64,
+    failures: u64,
+    errors: BTreeMap<&'static str, u64>,
+    last_check: f64,
+    maintenance: bool,
+    parent_unreachable: bool,
+    flapping: bool,
+    handler_panics: u64,
+}
+
+/// Exporter collecting metrics of all checks performed by an [AsyncTargetExecutor].
+///
+/// Clones of an exporter share their metrics. Register a clone with
+/// [AsyncTargetExecutor::add_observer] and expose the metrics with [PrometheusExporter::serve]
+/// or [PrometheusExporter::render].
+#[derive(Clone, Debug, Default)]
+pub struct PrometheusExporter {
+    metrics: Arc<Mutex<BTreeMap<String, TargetMetrics>>>,
+}
+
+/// Handle of a running metrics HTTP listener. The listener is stopped on drop.
+pub struct MetricsServer {
+    server: HttpServer,
+}
+
+impl PrometheusExporter {
+    /// Construct a new [PrometheusExporter] without any metrics.
+    pub fn new() -> Self {
+        PrometheusExporter::default()
+    }
+
+    /// Render all collected metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let metrics = self.metrics.lock().unwrap();
+        let mut out = String::new();
+
+        family(
+            &mut out,
+            "reachable_up",
+            "gauge",
+            "Whether the target was reachable on the last check.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(&mut out, "reachable_up", id, None, if target.up { 1.0 } else { 0.0 });
+        }
+
+        family(
+            &mut out,
+            "reachable_check_duration_seconds",
+            "gauge",
+            "Duration of the last successful check, including all retries.",
+        );
+        for (id, target) in metrics.iter() {
+            if let Some(check_duration) = target.check_duration {
+                sample(&mut out, "reachable_check_duration_seconds", id, None, check_duration);
+            }
+        }
+
+        family(
+            &mut out,
+            "reachable_checks_total",
+            "counter",
+            "Number of performed checks.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(&mut out, "reachable_checks_total", id, None, target.checks as f64);
+        }
+
+        family(
+            &mut out,
+            "reachable_failures_total",
+            "counter",
+            "Number of checks reporting the target as not available.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(&mut out, "reachable_failures_total", id, None, target.failures as f64);
+        }
+
+        family(
+            &mut out,
+            "reachable_errors_total",
+            "counter",
+            "Number of checks failed with an error.",
+        );
+        for (id, target) in metrics.iter() {
+            for (kind, count) in target.errors.iter() {
+                sample(&mut out, "reachable_errors_total", id, Some(kind), *count as f64);
+            }
+        }
+
+        family(
+            &mut out,
+            "reachable_last_check_timestamp_seconds",
+            "gauge",
+            "Unix timestamp of the last check.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(
+                &mut out,
+                "reachable_last_check_timestamp_seconds",
+                id,
+                None,
+                target.last_check,
+            );
+        }
+
+        family(
+            &mut out,
+            "reachable_maintenance",
+            "gauge",
+            "Whether the last check happened during a maintenance window.",
+        );
+        for (id, target) in metrics.iter() {
+            let maintenance = if target.maintenance { 1.0 } else { 0.0 };
+            sample(&mut out, "reachable_maintenance", id, None, maintenance);
+        }
+
+        family(
+            &mut out,
+            "reachable_parent_unreachable",
+            "gauge",
+            "Whether the target was unreachable due to a parent on the last check.",
+        );
+        for (id, target) in metrics.iter() {
+            let parent_unreachable = if target.parent_unreachable { 1.0 } else { 0.0 };
+            sample(&mut out, "reachable_parent_unreachable", id, None, parent_unreachable);
+        }
+
+        family(
+            &mut out,
+            "reachable_flapping",
+            "gauge",
+            "Whether the target is flapping.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(
+                &mut out,
+                "reachable_flapping",
+                id,
+                None,
+                if target.flapping { 1.0 } else { 0.0 },
+            );
+        }
+
+        family(
+            &mut out,
+            "reachable_handler_panics_total",
+            "counter",
+            "Number of panics caught in the check handler.",
+        );
+        for (id, target) in metrics.iter() {
+            sample(
+                &mut out,
+                "reachable_handler_panics_total",
+                id,
+                None,
+                target.handler_panics as f64,
+            );
+        }
+        out
+    }
+
+    /// Serve the collected metrics on "/metrics" via HTTP on given address.
+    ///
+    /// # Errors
+    /// Returns an [io::Error] if the listener could not be bound.
+    pub fn serve<A: ToSocketAddrs>(&self, addr: A) -> io::Result<MetricsServer> {
+        let exporter = self.clone();
+        let routes: BoxedRoutes =
+            Box::new(
+                move |request: &Request| match (request.method.as_str(), request.path.as_str()) {
+                    ("GET", "/metrics") => Response::ok(CONTENT_TYPE, exporter.render()),
+                    (_, "/metrics") => Response::error(405),
+                    _ => Response::error(404),
+                },
+            );
+
+        let server = HttpServer::bind(addr, routes)?;
+        Ok(MetricsServer { server })
+    }
+}
+
+impl CheckObserver for PrometheusExporter {
+    fn on_check(&self, report: &CheckReport) {
+        let mut metrics = self.metrics.lock().unwrap();
+        let target = metrics.entry(String::from(report.id)).or_default();
+        let reachable = matches!(report.status, Status::Available | Status::Degraded(_));
+
+        target.up = reachable;
+        target.check_duration = reachable.then_some(report.duration.as_secs_f64());
+        target.checks += 1;
+        if *report.status == Status::NotAvailable {
+            target.failures += 1;
+        }
+        if let Some(error) = report.error {
+            *target.errors.entry(error_kind(error)).or_default() += 1;
+        }
+        target.last_check = report
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        target.maintenance = report.maintenance;
+        target.parent_unreachable = report.unreachable_parent.is_some();
+        target.flapping = report.flapping;
+    }
+
+    fn on_handler_panic(&self, id: &str, _message: &str) {
+        let mut metrics = self.metrics.lock().unwrap();
+        metrics.entry(String::from(id)).or_default().handler_panics += 1;
+    }
+
+    fn on_remove(&self, id: &str) {
+        self.metrics.lock().unwrap().remove(id);
+    }
+}
+
+impl MetricsServer {
+    /// Get the address the metrics are served on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.server.local_addr()
+    }
+}
+
+/// Get the label value describing the kind of given error.
+fn error_kind(error: &CheckTargetError) -> &'static str {
+    match error {
+        CheckTargetError::Message(_) => "message",
+        CheckTargetError::ResolveTargetError(_, _) => "resolve",
+        CheckTargetError::GenericError(_, _) => "generic",
+        CheckTargetError::Panic(_) => "panic",
+        CheckTargetError::UnreachableParent(_) => "unreachable_parent",
+    }
+}
+
+/// Write HELP and TYPE lines of a metric family.
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Write a single sample labeled with given target id and optional error kind.
+fn sample(out: &mut String, name: &str, id: &str, kind: Option<&str>, value: f64) {
+    let _ = match kind {
+        Some(kind) => writeln!(out, "{}{{target=\"{}\",kind=\"{}\"}} {}", name, escape(id), kind, value),
+        None => writeln!(out, "{}{{target=\"{}\"}} {}", name, escape(id), value),
+    };
+}
+
+/// Escape a label value as required by the text exposition format.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::http::tests::get;
+
+    fn report<'a>(id: &'a str, status: &'a Status, error: Option<&'a CheckTargetError>) -> CheckReport<'a> {
+        CheckReport::new(
+            id,
+            status,
+            &Status::Unknown,
+            error,
+            Duration::from_millis(250),
+            UNIX_EPOCH + Duration::from_secs(42),
+        )
+    }
+
+    #[test]
+    fn prometheus_exporter_render() {
+        // Expectency: Each check updates the metrics of its target, label values are escaped. Handler
+        //             panics are counted and removed targets are dropped from the output.
+        let exporter = PrometheusExporter::new();
+        let error = CheckTargetError::from("Error");
+        exporter.on_check(&report("a", &Status::Available, None));
+        exporter.on_check(&report("a", &Status::NotAvailable, None));
+        exporter.on_check(&report("b\"", &Status::Degraded(String::from("slow")), None));
+        exporter.on_check(&report("b\"", &Status::Unknown, Some(&error)));
+
+        let out = exporter.render();
+        assert!(out.contains("# TYPE reachable_up gauge\n"));
+        assert!(out.contains("reachable_up{target=\"a\"} 0\n"));
+        assert!(out.contains("reachable_up{target=\"b\\\"\"} 0\n"));
+        assert!(out.contains("reachable_checks_total{target=\"a\"} 2\n"));
+        assert!(out.contains("reachable_failures_total{target=\"a\"} 1\n"));
+        assert!(out.contains("reachable_failures_total{target=\"b\\\"\"} 0\n"));
+        assert!(out.contains("reachable_errors_total{target=\"b\\\"\",kind=\"message\"} 1\n"));
+        assert!(out.contains("reachable_last_check_timestamp_seconds{target=\"a\"} 42\n"));
+        assert!(!out.contains("reachable_check_duration_seconds{"));
+        assert!(out.contains("reachable_maintenance{target=\"a\"} 0\n"));
+        assert!(out.contains("reachable_parent_unreachable{target=\"a\"} 0\n"));
+        assert!(out.contains("reachable_flapping{target=\"a\"} 0\n"));
+
+        exporter.on_handler_panic("a", "Boom");
+        assert!(exporter
+            .render()
+            .contains("reachable_handler_panics_total{target=\"a\"} 1\n"));
+
+        exporter.on_check(&report("a", &Status::Available, None));
+        assert!(exporter
+            .render()
+            .contains("reachable_check_duration_seconds{target=\"a\"} 0.25\n"));
+
+        exporter.on_remove("a");
+        assert!(!exporter.render().contains("target=\"a\""));
+    }
+
+    #[test]
+    fn prometheus_exporter_serve() {
+        // Expectency: The metrics are served on /metrics, other paths are answered with 404.
+        let exporter = PrometheusExporter::new();
+        exporter.on_check(&report("a", &Status::Available, None));
+
+        let server = exporter.serve("127.0.0.1:0").unwrap();
+        let (status, body) = get(server.local_addr(), "/metrics");
+        assert_eq!(status, 200);
+        assert!(body.contains("reachable_up{target=\"a\"} 1\n"));
+        assert_eq!(get(server.local_addr(), "/").0, 404);
+    }
+}
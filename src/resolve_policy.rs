@@ -18,7 +18,7 @@ use super::{IcmpTarget, TcpTarget};
 
 /// A ResolvePolicy allows control over IP address resolution of network targets
 /// like [IcmpTarget] and [TcpTarget].
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum ResolvePolicy {
     /// Resolve use all IP address versions
     Agnostic,
@@ -0,0 +1,525 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module containing time based schedules: cron expressions, check schedules and maintenance windows.
+//!
+//! All points in time are interpreted in UTC.
+
+// Imports
+use super::error::ParseScheduleError;
+use super::time::{days_from_civil, days_in_month, format_time, unix_time, CivilTime, MAX_YEAR};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of days searched for the next match of a cron expression. Covers a full leap year cycle.
+const MAX_SEARCH_DAYS: u64 = 8 * 366;
+
+/// Cron expression, matching points in time with a resolution of one minute.
+///
+/// An expression consists of five fields: minute (0-59), hour (0-23), day of month (1-31),
+/// month (1-12) and day of week (0-7, 0 and 7 are Sunday). Each field accepts "*", single
+/// values, ranges like "1-5", steps like "*/15" or "0-30/10" and comma separated lists of these.
+/// If both day of month and day of week are restricted, a day matching either of them matches. Like
+/// in standard cron, a field starting with "*", e.g. "*/2", is not considered restricted.
+///
+/// The shortcuts "@hourly", "@daily", "@weekly", "@monthly" and "@yearly" are supported as well.
+///
+/// # Example
+/// ```
+/// # use std::str::FromStr;
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// # use reachable::schedule::CronSchedule;
+/// // Every night at 02:30
+/// let schedule = CronSchedule::from_str("30 2 * * *").unwrap();
+///
+/// // 2021-10-03 02:30:00 UTC
+/// assert!(schedule.matches(UNIX_EPOCH + Duration::from_secs(1633228200)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSchedule {
+    /// Check if given point in time matches this schedule. Seconds are ignored.
+    pub fn matches(&self, time: SystemTime) -> bool {
+        let time = CivilTime::from(time);
+        self.matches_day(&time) && contains(self.minutes, time.minute) && contains(self.hours, time.hour)
+    }
+
+    /// Get the first point in time after given point in time, matching this schedule.
+    ///
+    /// # Returns
+    /// * The full minute of the next match.
+    /// * None, if the schedule does not match within the next eight years, e.g. "0 0 30 2 *".
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        let start = unix_time(time) / 60 * 60 + 60;
+        let first_day = start / 86400;
+
+        for day in first_day..first_day + MAX_SEARCH_DAYS {
+            if !self.matches_day(&CivilTime::from(day * 86400)) {
+                continue;
+            }
+
+            // Note: Only on the first day, the search starts within the day
+            let offset = if day == first_day { start % 86400 } else { 0 };
+            for hour in (offset / 3600..24).filter(|hour| contains(self.hours, *hour)) {
+                let first_minute = if hour == offset / 3600 { offset % 3600 / 60 } else { 0 };
+                if let Some(minute) = (first_minute..60).find(|minute| contains(self.minutes, *minute)) {
+                    return Some(UNIX_EPOCH + Duration::from_secs(day * 86400 + hour * 3600 + minute * 60));
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the last full minute at or before given seconds since the unix epoch, matching this
+    /// schedule. The search covers the days back to given earliest point in time, at most eight years.
+    fn last_at_or_before(&self, time: u64, earliest: u64) -> Option<u64> {
+        let last_day = time / 86400;
+        let first_day = (earliest / 86400).max(last_day.saturating_sub(MAX_SEARCH_DAYS));
+
+        for day in (first_day..=last_day).rev() {
+            if !self.matches_day(&CivilTime::from(day * 86400)) {
+                continue;
+            }
+
+            // Note: Only on the last day, the search starts within the day
+            let offset = if day == last_day { time % 86400 } else { 86399 };
+            for hour in (0..=offset / 3600).rev().filter(|hour| contains(self.hours, *hour)) {
+                let last_minute = if hour == offset / 3600 { offset % 3600 / 60 } else { 59 };
+                if let Some(minute) = (0..=last_minute).rev().find(|minute| contains(self.minutes, *minute)) {
+                    return Some(day * 86400 + hour * 3600 + minute * 60);
+                }
+            }
+        }
+        None
+    }
+
+    /// Check if the month and day of given time match this schedule.
+    fn matches_day(&self, time: &CivilTime) -> bool {
+        let day = match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => contains(self.days, time.day),
+            (true, false) => contains(self.weekdays, time.weekday),
+            (false, false) => contains(self.days, time.day) || contains(self.weekdays, time.weekday),
+        };
+        day && contains(self.months, time.month)
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = match s.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" => "0 0 1 1 *",
+            expression => expression,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(ParseScheduleError::from("Cron expression requires five fields"));
+        }
+
+        // Note: Sunday is accepted as 0 and 7
+        let weekdays = parse_field(fields[4], 0, 7, "Invalid day of week")?;
+        Ok(CronSchedule {
+            minutes: parse_field(fields[0], 0, 59, "Invalid minute")?,
+            hours: parse_field(fields[1], 0, 23, "Invalid hour")?,
+            days: parse_field(fields[2], 1, 31, "Invalid day of month")?,
+            months: parse_field(fields[3], 1, 12, "Invalid month")?,
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            // Note: Like in standard cron, fields starting with "*" do not restrict the day.
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*'),
+        })
+    }
+}
+
+/// Wall-clock based schedule of availability checks, an alternative to check intervals.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use reachable::schedule::CheckSchedule;
+/// // Every 5 minutes at :00, :05, :10, ...
+/// let schedule = CheckSchedule::Aligned {
+///     interval: Duration::from_secs(5 * 60),
+///     offset: Duration::ZERO,
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum CheckSchedule {
+    /// Check at each match of a cron expression.
+    Cron(CronSchedule),
+    /// Check at each multiple of interval since the unix epoch, shifted by offset. Intervals
+    /// dividing a day are thereby aligned to midnight UTC.
+    Aligned { interval: Duration, offset: Duration },
+}
+
+impl CheckSchedule {
+    /// Get the first point in time after given point in time, a check is due.
+    ///
+    /// # Returns
+    /// * The point in time of the next check.
+    /// * None, if no check is due anymore: the cron expression does not match within the next
+    ///   eight years or the interval is zero.
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        match self {
+            CheckSchedule::Cron(schedule) => schedule.next_after(time),
+            CheckSchedule::Aligned { interval, offset } => {
+                let interval = interval.as_nanos();
+                if interval == 0 {
+                    return None;
+                }
+
+                let offset = offset.as_nanos() % interval;
+                let now = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+                let next = match now.checked_sub(offset) {
+                    Some(elapsed) => (elapsed / interval + 1) * interval + offset,
+                    None => offset,
+                };
+                let next = Duration::new((next / 1_000_000_000) as u64, (next % 1_000_000_000) as u32);
+                Some(UNIX_EPOCH + next)
+            }
+        }
+    }
+}
+
+impl From<CronSchedule> for CheckSchedule {
+    fn from(schedule: CronSchedule) -> Self {
+        CheckSchedule::Cron(schedule)
+    }
+}
+
+/// Time range, during which a target is expected to be unavailable, e.g. a nightly backup.
+///
+/// # Example
+/// ```
+/// # use std::str::FromStr;
+/// # use std::time::Duration;
+/// # use reachable::schedule::{CronSchedule, MaintenanceWindow};
+/// // Every night from 01:00 until 03:00
+/// let window = MaintenanceWindow::Recurring {
+///     schedule: CronSchedule::from_str("0 1 * * *").unwrap(),
+///     duration: Duration::from_secs(2 * 60 * 60),
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum MaintenanceWindow {
+    /// One-off window from start (inclusive) until end (exclusive).
+    Once { start: SystemTime, end: SystemTime },
+    /// Recurring window, beginning at each match of the schedule and lasting for duration.
+    Recurring { schedule: CronSchedule, duration: Duration },
+}
+
+impl MaintenanceWindow {
+    /// Check if this window is active at given point in time.
+    pub fn is_active(&self, time: SystemTime) -> bool {
+        match self {
+            MaintenanceWindow::Once { start, end } => *start <= time && time < *end,
+            MaintenanceWindow::Recurring { schedule, duration } => {
+                // Look for the latest window beginning within the last duration
+                let now = unix_time(time);
+                schedule
+                    .last_at_or_before(now, now.saturating_sub(duration.as_secs()))
+                    .is_some_and(|begin| begin.saturating_add(duration.as_secs()) > now)
+            }
+        }
+    }
+}
+
+/// Parse a point in time in UTC, formatted as `YYYY-MM-DDTHH:MM[:SS][Z]`. The date and time may be
+/// separated by a space as well. Years from 1970 up to 9999 are accepted.
+pub fn parse_utc_time(s: &str) -> Result<SystemTime, ParseScheduleError> {
+    let invalid = || ParseScheduleError::from("Invalid time, expected YYYY-MM-DDTHH:MM[:SS]");
+    let s = s.trim().trim_end_matches('Z');
+    let (date, time) = s.split_once(['T', ' ']).ok_or_else(invalid)?;
+
+    let number = |value: &str| {
+        value
+            .parse::<u64>()
+            .map_err(|error| ParseScheduleError::from(("Invalid time", error)))
+    };
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = time.split(':').collect();
+    if date.len() != 3 || !(2..=3).contains(&time.len()) {
+        return Err(invalid());
+    }
+
+    let (year, month, day) = (number(date[0])?, number(date[1])?, number(date[2])?);
+    let (hour, minute) = (number(time[0])?, number(time[1])?);
+    let second = time.get(2).map(|second| number(second)).transpose()?.unwrap_or(0);
+    if !(1970..=MAX_YEAR).contains(&year) || !(1..=12).contains(&month) || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    if !(1..=days_in_month(year, month)).contains(&day) {
+        return Err(invalid());
+    }
+
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Format a point in time as UTC date and time, e.g. "2021-10-03 12:00:00". Points in time
+/// before the unix epoch are formatted as epoch.
+pub fn format_utc_time(time: SystemTime) -> String {
+    format_time(unix_time(time))
+}
+
+/// Check if given value is part of given bit set.
+fn contains(set: u64, value: u64) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Parse a single field of a cron expression into a bit set of all matching values.
+fn parse_field(field: &str, min: u64, max: u64, error: &'static str) -> Result<u64, ParseScheduleError> {
+    let number = |value: &str| match value.parse::<u64>() {
+        Ok(value) if (min..=max).contains(&value) => Ok(value),
+        Ok(_) => Err(ParseScheduleError::from(error)),
+        Err(parse_error) => Err(ParseScheduleError::from((error, parse_error))),
+    };
+
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u64>() {
+                Ok(step) if step > 0 => (range, step),
+                Ok(_) => return Err(ParseScheduleError::from(error)),
+                Err(parse_error) => return Err(ParseScheduleError::from((error, parse_error))),
+            },
+            None => (part, 1),
+        };
+
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((first, last)) => (number(first)?, number(last)?),
+            // Note: A single value with a step, e.g. "5/15", starts a range up to the maximum
+            None if part.contains('/') => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if first > last {
+            return Err(ParseScheduleError::from(error));
+        }
+        set |= (first..=last)
+            .step_by(step as usize)
+            .fold(0, |set, value| set | 1 << value);
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> SystemTime {
+        parse_utc_time(s).unwrap()
+    }
+
+    #[test]
+    fn cron_schedule_matches() {
+        // Expectency: A cron expression matches all points in time, its fields describe.
+        let schedule = CronSchedule::from_str("*/15 9-17 * * 1-5").unwrap();
+        assert!(schedule.matches(time("2021-10-04T09:00:00Z")));
+        assert!(schedule.matches(time("2021-10-04T17:45:59Z")));
+        assert!(!schedule.matches(time("2021-10-04T09:10:00Z")));
+        assert!(!schedule.matches(time("2021-10-04T18:00:00Z")));
+        assert!(!schedule.matches(time("2021-10-03T09:00:00Z")));
+
+        // Day of month or day of week, Sunday as 7
+        let schedule = CronSchedule::from_str("0 0 1 * 7").unwrap();
+        assert!(schedule.matches(time("2021-10-01T00:00:00Z")));
+        assert!(schedule.matches(time("2021-10-03T00:00:00Z")));
+        assert!(!schedule.matches(time("2021-10-02T00:00:00Z")));
+
+        let schedule = CronSchedule::from_str("@yearly").unwrap();
+        assert!(schedule.matches(time("2024-01-01 00:00")));
+        assert!(!schedule.matches(time("2024-02-29 00:00")));
+    }
+
+    #[test]
+    fn cron_schedule_next_after() {
+        // Expectency: The next match is the first matching full minute after given time.
+        let schedule = CronSchedule::from_str("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:00:00Z")),
+            Some(time("2021-10-04T09:15:00Z"))
+        );
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:14:59Z")),
+            Some(time("2021-10-04T09:15:00Z"))
+        );
+        assert_eq!(
+            schedule.next_after(time("2021-10-08T17:45:00Z")),
+            Some(time("2021-10-11T09:00:00Z"))
+        );
+
+        let schedule = CronSchedule::from_str("0 0 29 2 *").unwrap();
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:00:00Z")),
+            Some(time("2024-02-29T00:00:00Z"))
+        );
+        assert_eq!(
+            CronSchedule::from_str("0 0 30 2 *")
+                .unwrap()
+                .next_after(time("2021-10-04T09:00")),
+            None
+        );
+    }
+
+    #[test]
+    fn cron_schedule_star_step_day() {
+        // Expectency: Fields starting with "*" do not restrict the day. Only the other day field
+        //             applies, instead of matching either of them.
+        let monday = time("2021-10-04T00:00:00Z");
+        let tuesday = time("2021-10-05T00:00:00Z");
+        let schedule = CronSchedule::from_str("0 0 */1 * 1").unwrap();
+        assert!(schedule.matches(monday));
+        assert!(!schedule.matches(tuesday));
+        let schedule = CronSchedule::from_str("0 0 4 * */1").unwrap();
+        assert!(schedule.matches(monday));
+        assert!(!schedule.matches(tuesday));
+        let schedule = CronSchedule::from_str("0 0 5 * 1").unwrap();
+        assert!(schedule.matches(monday));
+        assert!(schedule.matches(tuesday));
+    }
+
+    #[test]
+    fn check_schedule_next_after() {
+        // Expectency: Aligned schedules are due at multiples of the interval plus offset, cron
+        //             schedules at the next match.
+        let schedule = CheckSchedule::Aligned {
+            interval: Duration::from_secs(300),
+            offset: Duration::from_secs(30),
+        };
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:00:00Z")),
+            Some(time("2021-10-04T09:00:30Z"))
+        );
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:00:30Z")),
+            Some(time("2021-10-04T09:05:30Z"))
+        );
+        assert_eq!(
+            schedule.next_after(UNIX_EPOCH),
+            Some(UNIX_EPOCH + Duration::from_secs(30))
+        );
+
+        let schedule = CheckSchedule::Aligned {
+            interval: Duration::ZERO,
+            offset: Duration::ZERO,
+        };
+        assert_eq!(schedule.next_after(UNIX_EPOCH), None);
+
+        let schedule = CheckSchedule::from(CronSchedule::from_str("@hourly").unwrap());
+        assert_eq!(
+            schedule.next_after(time("2021-10-04T09:00:00Z")),
+            Some(time("2021-10-04T10:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn cron_schedule_from_str_invalid() {
+        // Expectency: Invalid expressions are rejected with a message naming the invalid field.
+        let error = |s| CronSchedule::from_str(s).unwrap_err().to_string();
+        assert_eq!(error("* * * *"), "Cron expression requires five fields");
+        assert_eq!(error("60 * * * *"), "Invalid minute");
+        assert_eq!(error("* 5-2 * * *"), "Invalid hour");
+        assert_eq!(error("* * 0 * *"), "Invalid day of month");
+        assert_eq!(error("* * * */0 *"), "Invalid month");
+        assert_eq!(
+            error("* * * * mon"),
+            "Invalid day of week caused by: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn maintenance_window_is_active() {
+        // Expectency: Windows are active from their start until their end or duration elapsed.
+        let window = MaintenanceWindow::Once {
+            start: time("2021-10-03T01:00:00Z"),
+            end: time("2021-10-03T03:00:00Z"),
+        };
+        assert!(!window.is_active(time("2021-10-03T00:59:59Z")));
+        assert!(window.is_active(time("2021-10-03T01:00:00Z")));
+        assert!(!window.is_active(time("2021-10-03T03:00:00Z")));
+
+        // Recurring window crossing midnight
+        let window = MaintenanceWindow::Recurring {
+            schedule: CronSchedule::from_str("30 23 * * *").unwrap(),
+            duration: Duration::from_secs(60 * 60),
+        };
+        assert!(window.is_active(time("2021-10-03T23:30:00Z")));
+        assert!(window.is_active(time("2021-10-04T00:29:59Z")));
+        assert!(!window.is_active(time("2021-10-04T00:30:00Z")));
+        assert!(!window.is_active(time("2021-10-03T23:29:59Z")));
+    }
+
+    #[test]
+    fn maintenance_window_is_active_long_duration() {
+        // Expectency: Long recurring windows are found by searching backwards for the latest
+        //             match, even if it began weeks ago.
+        let window = MaintenanceWindow::Recurring {
+            schedule: CronSchedule::from_str("0 0 1 * *").unwrap(),
+            duration: Duration::from_secs(720 * 60 * 60),
+        };
+        assert!(window.is_active(time("2021-10-01T00:00:00Z")));
+        assert!(window.is_active(time("2021-10-30T23:59:59Z")));
+        assert!(!window.is_active(time("2021-10-31T00:00:00Z")));
+        assert!(!window.is_active(time("2021-08-31T12:00:00Z")));
+
+        // Durations exceeding the search range still find the latest match
+        let window = MaintenanceWindow::Recurring {
+            schedule: CronSchedule::from_str("0 0 1 1 *").unwrap(),
+            duration: Duration::MAX,
+        };
+        assert!(window.is_active(time("2021-10-03T12:00:00Z")));
+        let window = MaintenanceWindow::Recurring {
+            schedule: CronSchedule::from_str("0 0 30 2 *").unwrap(),
+            duration: Duration::MAX,
+        };
+        assert!(!window.is_active(time("2021-10-03T12:00:00Z")));
+    }
+
+    #[test]
+    fn parse_utc_time_formats() {
+        // Expectency: Date and time are parsed with optional seconds and time zone designator.
+        let expected = UNIX_EPOCH + Duration::from_secs(1633262400);
+        assert_eq!(parse_utc_time("2021-10-03T12:00:00Z").unwrap(), expected);
+        assert_eq!(parse_utc_time("2021-10-03 12:00").unwrap(), expected);
+        assert_eq!(
+            parse_utc_time("2020-02-29T00:00:00Z").unwrap(),
+            UNIX_EPOCH + Duration::from_secs(1582934400)
+        );
+        assert!(parse_utc_time("2021-10-03").is_err());
+        assert!(parse_utc_time("2021-13-03T12:00").is_err());
+        assert!(parse_utc_time("2021-02-29T00:00").is_err());
+        assert!(parse_utc_time("2021-02-31T00:00").is_err());
+        assert!(parse_utc_time("2021-04-31T00:00").is_err());
+        assert!(parse_utc_time("9999-12-31T23:59:59").is_ok());
+        assert!(parse_utc_time("10000-01-01T00:00").is_err());
+        assert!(parse_utc_time("300000000000-01-01T00:00").is_err());
+    }
+
+    #[test]
+    fn format_utc_time_roundtrip() {
+        // Expectency: Formatted points in time are parsed back into the same point in time.
+        let time = time("2021-10-03T12:00:00Z");
+        assert_eq!(format_utc_time(time), "2021-10-03 12:00:00");
+        assert_eq!(parse_utc_time(&format_utc_time(time)).unwrap(), time);
+    }
+}
@@ -0,0 +1,344 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains an embedded HTTP status API for the targets of an [AsyncTargetExecutor].
+//!
+//! The [StatusApi] is a [CheckObserver] tracking the state of all targets. Once served, it
+//! offers the following routes:
+//! * `/`: HTML status page listing all targets.
+//! * `/targets`: JSON array containing the [TargetState] of all targets.
+//! * `/targets/{id}`: JSON object containing the [TargetState] of the target with given
+//!   (percent encoded) id.
+//!
+//! # Example
+//! ```no_run
+//! # use reachable::*;
+//! # use reachable::status_api::StatusApi;
+//! let status_api = StatusApi::new();
+//! let mut exec = AsyncTargetExecutor::new();
+//! exec.add_observer(status_api.clone());
+//!
+//! // Serve status page on http://127.0.0.1:8080/ until server is dropped.
+//! let server = status_api.serve("127.0.0.1:8080").unwrap();
+//! ```
+//!
+//! # Notes
+//! Requires crate to be configured with feature "status_api".
+
+use super::async_target::{CheckObserver, CheckReport};
+use super::http::{self, BoxedRoutes, HttpServer, Request, Response};
+use super::time::{format_time, unix_time};
+use super::Status;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io::{self};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+// Documentation imports
+#[cfg(doc)]
+use super::AsyncTargetExecutor;
+
+/// Content type of JSON responses.
+const JSON: &str = "application/json";
+
+/// Content type of HTML responses.
+const HTML: &str = "text/html; charset=utf-8";
+
+/// State of a single target, as reported by the [StatusApi].
+///
+/// All points in time are given in seconds since the unix epoch.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TargetState {
+    /// Identifier of the target.
+    pub id: String,
+    /// Current status: "unknown", "available", "degraded" or "not_available".
+    pub status: String,
+    /// Reason of the degradation, if the status is "degraded".
+    pub reason: Option<String>,
+    /// Point in time of the last check.
+    pub last_check: u64,
+    /// Point in time the status changed last.
+    pub last_change: Option<u64>,
+    /// Message of the last error occurred during a check.
+    pub last_error: Option<String>,
+    /// Point in time the last error occurred.
+    pub last_error_time: Option<u64>,
+    /// True, if the last check happened during a maintenance window.
+    pub maintenance: bool,
+    /// Identifier of the parent, the target was unreachable due to on the last check.
+    pub unreachable_parent: Option<String>,
+    /// True, if the target is flapping.
+    pub flapping: bool,
+}
+
+/// Status API collecting the state of all targets checked by an [AsyncTargetExecutor].
+///
+/// Clones of a status API share their state. Register a clone with
+/// [AsyncTargetExecutor::add_observer] and expose the state with [StatusApi::serve].
+#[derive(Clone, Debug, Default)]
+pub struct StatusApi {
+    targets: Arc<Mutex<BTreeMap<String, TargetState>>>,
+}
+
+/// Handle of a running status API HTTP server. The server is stopped on drop.
+pub struct StatusServer {
+    server: HttpServer,
+}
+
+impl StatusApi {
+    /// Construct a new [StatusApi] without any targets.
+    pub fn new() -> Self {
+        StatusApi::default()
+    }
+
+    /// Get the state of all targets, ordered by id.
+    pub fn targets(&self) -> Vec<TargetState> {
+        self.targets.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Get the state of the target with given id.
+    pub fn target(&self, id: &str) -> Option<TargetState> {
+        self.targets.lock().unwrap().get(id).cloned()
+    }
+
+    /// Render the HTML status page.
+    pub fn render_html(&self) -> String {
+        let mut out = String::from(concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta http-equiv=\"refresh\" content=\"10\">\n",
+            "<title>reachable status</title>\n</head>\n<body>\n<h1>reachable status</h1>\n<table border=\"1\">\n",
+            "<tr><th>Target</th><th>Status</th><th>Last change</th><th>Last check</th><th>Last error</th></tr>\n",
+        ));
+        for target in self.targets().iter() {
+            let mut status = match &target.reason {
+                Some(reason) => format!("{} ({})", target.status, reason),
+                None => target.status.clone(),
+            };
+            if target.maintenance {
+                status.push_str(", maintenance");
+            }
+            if target.flapping {
+                status.push_str(", flapping");
+            }
+            if let Some(parent) = &target.unreachable_parent {
+                status.push_str(&format!(", unreachable due to parent {}", parent));
+            }
+            let last_error = match (&target.last_error, target.last_error_time) {
+                (Some(error), Some(time)) => format!("{}: {}", format_time(time), error),
+                _ => String::new(),
+            };
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&target.id),
+                escape_html(&status),
+                target.last_change.map(format_time).unwrap_or_default(),
+                format_time(target.last_check),
+                escape_html(&last_error)
+            );
+        }
+        out.push_str("</table>\n</body>\n</html>\n");
+        out
+    }
+
+    /// Serve the status API via HTTP on given address.
+    ///
+    /// # Errors
+    /// Returns an [io::Error] if the listener could not be bound.
+    pub fn serve<A: ToSocketAddrs>(&self, addr: A) -> io::Result<StatusServer> {
+        let status_api = self.clone();
+        let routes: BoxedRoutes = Box::new(move |request: &Request| status_api.route(request));
+
+        let server = HttpServer::bind(addr, routes)?;
+        Ok(StatusServer { server })
+    }
+
+    /// Answer given request.
+    fn route(&self, request: &Request) -> Response {
+        if request.method != "GET" {
+            return Response::error(405);
+        }
+
+        match request.path.as_str() {
+            "/" => Response::ok(HTML, self.render_html()),
+            "/targets" => to_json(&self.targets()),
+            path => match path.strip_prefix("/targets/").and_then(http::percent_decode) {
+                Some(id) => match self.target(&id) {
+                    Some(target) => to_json(&target),
+                    None => Response::error(404),
+                },
+                None => Response::error(404),
+            },
+        }
+    }
+}
+
+impl CheckObserver for StatusApi {
+    fn on_check(&self, report: &CheckReport) {
+        let now = unix_time(report.timestamp);
+        let (status, reason) = match report.status {
+            Status::Unknown => ("unknown", None),
+            Status::Available => ("available", None),
+            Status::Degraded(reason) => ("degraded", Some(reason.clone())),
+            Status::NotAvailable => ("not_available", None),
+        };
+
+        let mut targets = self.targets.lock().unwrap();
+        let target = targets.entry(String::from(report.id)).or_insert_with(|| TargetState {
+            id: String::from(report.id),
+            status: String::from(status),
+            reason: None,
+            last_check: now,
+            last_change: None,
+            last_error: None,
+            last_error_time: None,
+            maintenance: false,
+            unreachable_parent: None,
+            flapping: false,
+        });
+
+        if !report.status.same_state(report.old_status) {
+            target.last_change = Some(now);
+        }
+        if let Some(error) = report.error {
+            target.last_error = Some(error.to_string());
+            target.last_error_time = Some(now);
+        }
+        target.status = String::from(status);
+        target.reason = reason;
+        target.last_check = now;
+        target.maintenance = report.maintenance;
+        target.unreachable_parent = report.unreachable_parent.map(String::from);
+        target.flapping = report.flapping;
+    }
+
+    fn on_handler_panic(&self, id: &str, message: &str) {
+        if let Some(target) = self.targets.lock().unwrap().get_mut(id) {
+            target.last_error = Some(format!("Handler panicked: {}", message));
+            target.last_error_time = Some(unix_time(SystemTime::now()));
+        }
+    }
+
+    fn on_remove(&self, id: &str) {
+        self.targets.lock().unwrap().remove(id);
+    }
+}
+
+impl StatusServer {
+    /// Get the address the status API is served on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.server.local_addr()
+    }
+}
+
+/// Serialize given value into a JSON response.
+fn to_json<T: Serialize>(value: &T) -> Response {
+    match serde_json::to_string(value) {
+        Ok(body) => Response::ok(JSON, body),
+        Err(_) => Response::error(500),
+    }
+}
+
+/// Escape given text for use in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+    use crate::http::tests::get;
+    use crate::CheckTargetError;
+
+    fn report<'a>(
+        id: &'a str,
+        status: &'a Status,
+        old_status: &'a Status,
+        error: Option<&'a CheckTargetError>,
+        time: u64,
+    ) -> CheckReport<'a> {
+        CheckReport::new(
+            id,
+            status,
+            old_status,
+            error,
+            Duration::from_millis(10),
+            UNIX_EPOCH + Duration::from_secs(time),
+        )
+    }
+
+    #[test]
+    fn status_api_track_state() {
+        // Expectency: Checks update status, last change and last error of their target. Handler
+        //             panics are stored as last error and removed targets are dropped.
+        let status_api = StatusApi::new();
+        let error = CheckTargetError::from("Error");
+        let slow = Status::Degraded(String::from("slow"));
+        status_api.on_check(&report("a", &Status::Available, &Status::Unknown, None, 10));
+        status_api.on_check(&report("a", &Status::Available, &Status::Available, None, 20));
+        status_api.on_check(&report("a", &slow, &Status::Available, None, 30));
+        status_api.on_check(&report("a", &slow, &slow, Some(&error), 40));
+
+        let expected = TargetState {
+            id: String::from("a"),
+            status: String::from("degraded"),
+            reason: Some(String::from("slow")),
+            last_check: 40,
+            last_change: Some(30),
+            last_error: Some(String::from("Error")),
+            last_error_time: Some(40),
+            maintenance: false,
+            unreachable_parent: None,
+            flapping: false,
+        };
+        assert_eq!(status_api.target("a"), Some(expected));
+
+        status_api.on_handler_panic("a", "Boom");
+        let last_error = status_api.target("a").unwrap().last_error;
+        assert_eq!(last_error, Some(String::from("Handler panicked: Boom")));
+
+        status_api.on_remove("a");
+        assert!(status_api.targets().is_empty());
+    }
+
+    #[test]
+    fn status_api_serve() {
+        // Expectency: All targets and single targets (by percent encoded id) are served as JSON, the
+        //             status page escapes HTML. Unknown targets and paths are answered with 404.
+        let status_api = StatusApi::new();
+        status_api.on_check(&report("host:80", &Status::NotAvailable, &Status::Unknown, None, 0));
+        status_api.on_check(&report("<b>", &Status::Available, &Status::Unknown, None, 0));
+        let server = status_api.serve("127.0.0.1:0").unwrap();
+        let addr = server.local_addr();
+
+        let (status, body) = get(addr, "/targets");
+        assert_eq!(status, 200);
+        let targets: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(targets.as_array().unwrap().len(), 2);
+
+        let (status, body) = get(addr, "/targets/host%3A80");
+        assert_eq!(status, 200);
+        let target: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(target["status"], "not_available");
+        assert_eq!(target["last_change"], 0);
+        assert!(target["last_error"].is_null());
+
+        let (status, body) = get(addr, "/");
+        assert_eq!(status, 200);
+        assert!(body.contains("<td>&lt;b&gt;</td><td>available</td>"));
+        assert!(body.contains("<td>host:80</td><td>not_available</td><td>1970-01-01 00:00:00</td>"));
+
+        assert_eq!(get(addr, "/targets/missing").0, 404);
+        assert_eq!(get(addr, "/missing").0, 404);
+    }
+}
@@ -9,12 +9,13 @@
 // Imports
 use super::{CheckTargetError, ParseTargetError, ResolvePolicy};
 use std::convert::From;
+use std::error::Error;
 use std::fmt::{self};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6, TcpStream};
 use std::num::ParseIntError;
 use std::process::{Command, Stdio};
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // Test imports
 #[cfg(test)]
@@ -23,6 +24,12 @@ use mockall::automock;
 /// Default timeout duration for each connection attempt of a [TcpTarget]
 pub const DEFAULT_TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Default number of ICMP echo requests sent per address of an [IcmpTarget]
+pub const DEFAULT_ICMP_ECHO_COUNT: u32 = 1;
+
+/// Default duration to wait for each ICMP echo reply of an [IcmpTarget]
+pub const DEFAULT_ICMP_ECHO_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Alias of String expressing a "fully qualified domain name"
 pub type Fqhn = String;
 
@@ -76,15 +83,35 @@ pub enum Status {
     Unknown,
     /// A [Target] is available
     Available,
+    /// A [Target] is available, but impaired. Contains the reason of the degradation.
+    Degraded(String),
     /// A [Target] is not available
     NotAvailable,
 }
 
+impl Status {
+    /// Check if two [Status] describe the same state, ignoring the reason of a degradation.
+    ///
+    /// # Example
+    /// ```
+    /// # use reachable::Status;
+    ///
+    /// let slow = Status::Degraded(String::from("slow"));
+    /// let lossy = Status::Degraded(String::from("lossy"));
+    /// assert!(slow.same_state(&lossy));
+    /// assert!(!slow.same_state(&Status::Available));
+    /// ```
+    pub fn same_state(&self, other: &Status) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
 impl fmt::Display for Status {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Status::Unknown => write!(formatter, "unknown"),
             Status::Available => write!(formatter, "available"),
+            Status::Degraded(reason) => write!(formatter, "degraded ({})", reason),
             Status::NotAvailable => write!(formatter, "not available"),
         }
     }
@@ -96,12 +123,55 @@ impl fmt::Display for Status {
 /// IcmpTargets use the ping command to perform availability checks.
 /// Some administrator blackhole ICMP packets, leading to systems that look unavailable
 /// although they can be reached with a [TcpTarget].
+///
+/// The flags of ping differ between platforms. Linux (iputils), macOS and FreeBSD are supported.
+/// On other platforms, the echo timeout is left to ping and checks with a TTL or DSCP value fail.
+///
+/// The echo requests sent can be configured by chaining the setters of this type:
+/// ```
+/// # use std::str::FromStr;
+/// # use std::time::Duration;
+/// # use reachable::IcmpTarget;
+///
+/// let target = IcmpTarget::from_str("127.0.0.1")
+///     .unwrap()
+///     .set_echo_count(5)
+///     .set_echo_timeout(Duration::from_millis(500))
+///     .set_success_ratio(0.6)
+///     .set_ttl(16)
+///     .set_payload_size(128)
+///     .set_dscp(46);
+/// ```
 #[derive(Debug)]
 pub struct IcmpTarget {
     /// [Fqhn] specifying a system to connect to.
     fqhn: Fqhn,
     /// [ResolvePolicy] to apply during resolution of fqhn to IP addresses.
     resolve_policy: ResolvePolicy,
+    /// Number of echo requests sent to each resolved address.
+    echo_count: u32,
+    /// [Duration] to wait for each echo reply.
+    echo_timeout: Duration,
+    /// Optional [Duration] between two echo requests.
+    echo_interval: Option<Duration>,
+    /// Ratio of echo replies required to consider an address available.
+    success_ratio: f64,
+    /// Optional maximum average round trip time to consider an address available.
+    max_rtt: Option<Duration>,
+    /// Optional maximum round trip time deviation (jitter) to consider an address available.
+    max_jitter: Option<Duration>,
+    /// Optional average round trip time, above which an address is considered degraded.
+    degraded_rtt: Option<Duration>,
+    /// Optional round trip time deviation (jitter), above which an address is considered degraded.
+    degraded_jitter: Option<Duration>,
+    /// Optional packet loss ratio, above which an address is considered degraded.
+    degraded_loss_ratio: Option<f64>,
+    /// Optional TTL (IPv4) or hop limit (IPv6) of echo requests.
+    ttl: Option<u8>,
+    /// Optional payload size of echo requests in bytes.
+    payload_size: Option<u16>,
+    /// Optional DSCP value of echo requests.
+    dscp: Option<u8>,
 }
 
 impl IcmpTarget {
@@ -120,6 +190,18 @@ impl IcmpTarget {
         IcmpTarget {
             fqhn,
             resolve_policy,
+            echo_count: DEFAULT_ICMP_ECHO_COUNT,
+            echo_timeout: DEFAULT_ICMP_ECHO_TIMEOUT,
+            echo_interval: None,
+            success_ratio: 0.0,
+            max_rtt: None,
+            max_jitter: None,
+            degraded_rtt: None,
+            degraded_jitter: None,
+            degraded_loss_ratio: None,
+            ttl: None,
+            payload_size: None,
+            dscp: None,
         }
     }
 
@@ -129,6 +211,87 @@ impl IcmpTarget {
         self
     }
 
+    /// Set the number of echo requests sent to each resolved address. A value of 0 is treated as 1.
+    pub fn set_echo_count(mut self, echo_count: u32) -> Self {
+        self.echo_count = echo_count.max(1);
+        self
+    }
+
+    /// Set the [Duration] to wait for each echo reply.
+    pub fn set_echo_timeout(mut self, echo_timeout: Duration) -> Self {
+        self.echo_timeout = echo_timeout;
+        self
+    }
+
+    /// Set the [Duration] between two echo requests. Without root privileges, ping usually
+    /// refuses intervals below 200ms.
+    pub fn set_echo_interval(mut self, echo_interval: Duration) -> Self {
+        self.echo_interval = Some(echo_interval);
+        self
+    }
+
+    /// Set the ratio of echo replies required to consider an address available. The ratio is
+    /// clamped to [0.0, 1.0]. At least one reply is always required, the default is 0.0.
+    ///
+    /// # Notes
+    /// This is the inverse of the maximum tolerated packet loss ratio.
+    pub fn set_success_ratio(mut self, success_ratio: f64) -> Self {
+        self.success_ratio = success_ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the maximum average round trip time. Addresses exceeding it are considered not available.
+    pub fn set_max_rtt(mut self, max_rtt: Duration) -> Self {
+        self.max_rtt = Some(max_rtt);
+        self
+    }
+
+    /// Set the maximum round trip time deviation (jitter). Addresses exceeding it are considered
+    /// not available.
+    pub fn set_max_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = Some(max_jitter);
+        self
+    }
+
+    /// Set the average round trip time, above which an available address is reported as
+    /// [Status::Degraded].
+    pub fn set_degraded_rtt(mut self, degraded_rtt: Duration) -> Self {
+        self.degraded_rtt = Some(degraded_rtt);
+        self
+    }
+
+    /// Set the round trip time deviation (jitter), above which an available address is reported
+    /// as [Status::Degraded].
+    pub fn set_degraded_jitter(mut self, degraded_jitter: Duration) -> Self {
+        self.degraded_jitter = Some(degraded_jitter);
+        self
+    }
+
+    /// Set the packet loss ratio, above which an available address is reported as
+    /// [Status::Degraded]. The ratio is clamped to [0.0, 1.0].
+    pub fn set_degraded_loss_ratio(mut self, degraded_loss_ratio: f64) -> Self {
+        self.degraded_loss_ratio = Some(degraded_loss_ratio.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Set the TTL (IPv4) or hop limit (IPv6) of echo requests.
+    pub fn set_ttl(mut self, ttl: u8) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Set the payload size of echo requests in bytes.
+    pub fn set_payload_size(mut self, payload_size: u16) -> Self {
+        self.payload_size = Some(payload_size);
+        self
+    }
+
+    /// Set the DSCP value of echo requests. Only the lower six bits are used.
+    pub fn set_dscp(mut self, dscp: u8) -> Self {
+        self.dscp = Some(dscp & 0x3f);
+        self
+    }
+
     /// Get a reference to the [Fqhn].
     pub fn get_fqhn(&self) -> &Fqhn {
         &self.fqhn
@@ -138,6 +301,202 @@ impl IcmpTarget {
     pub fn get_resolve_policy(&self) -> &ResolvePolicy {
         &self.resolve_policy
     }
+
+    /// Get the number of echo requests sent to each resolved address.
+    pub fn get_echo_count(&self) -> u32 {
+        self.echo_count
+    }
+
+    /// Get a reference to the echo timeout [Duration] in use.
+    pub fn get_echo_timeout(&self) -> &Duration {
+        &self.echo_timeout
+    }
+
+    /// Get the [Duration] between two echo requests, if set.
+    pub fn get_echo_interval(&self) -> Option<&Duration> {
+        self.echo_interval.as_ref()
+    }
+
+    /// Get the ratio of echo replies required to consider an address available.
+    pub fn get_success_ratio(&self) -> f64 {
+        self.success_ratio
+    }
+
+    /// Get the maximum average round trip time, if set.
+    pub fn get_max_rtt(&self) -> Option<&Duration> {
+        self.max_rtt.as_ref()
+    }
+
+    /// Get the maximum round trip time deviation, if set.
+    pub fn get_max_jitter(&self) -> Option<&Duration> {
+        self.max_jitter.as_ref()
+    }
+
+    /// Get the average round trip time, above which an address is degraded, if set.
+    pub fn get_degraded_rtt(&self) -> Option<&Duration> {
+        self.degraded_rtt.as_ref()
+    }
+
+    /// Get the round trip time deviation, above which an address is degraded, if set.
+    pub fn get_degraded_jitter(&self) -> Option<&Duration> {
+        self.degraded_jitter.as_ref()
+    }
+
+    /// Get the packet loss ratio, above which an address is degraded, if set.
+    pub fn get_degraded_loss_ratio(&self) -> Option<f64> {
+        self.degraded_loss_ratio
+    }
+
+    /// Send echo requests to all resolved addresses and collect their statistics.
+    ///
+    /// # Returns
+    /// * On success, the [IcmpStatistics] of each resolved address.
+    /// * On failure, a [CheckTargetError]. Either the name resolution failed or ping could not
+    ///   be executed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::str::FromStr;
+    /// # use reachable::IcmpTarget;
+    ///
+    /// let target = IcmpTarget::from_str("127.0.0.1").unwrap().set_echo_count(10);
+    /// for statistics in target.check_statistics().unwrap() {
+    ///     println!("{}: {:.1}% loss", statistics.addr, statistics.loss_ratio() * 100.0);
+    /// }
+    /// ```
+    pub fn check_statistics(&self) -> Result<Vec<IcmpStatistics>, CheckTargetError> {
+        let addrs = self.resolve_policy.resolve(&self.fqhn)?;
+        addrs.into_iter().map(|addr| self.ping(addr)).collect()
+    }
+
+    /// Send echo requests to a single address with the ping command.
+    fn ping(&self, addr: IpAddr) -> Result<IcmpStatistics, CheckTargetError> {
+        let output = Command::new("ping")
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .args(self.ping_args(&addr, PingFlags::current())?)
+            .output()
+            .map_err(|error| CheckTargetError::from(("Failed to execute ping", Box::new(error) as Box<dyn Error>)))?;
+
+        // Evaluate the ping summary. If it is missing, fall back to the exit status of ping.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (transmitted, received) = match parse_ping_summary(&stdout) {
+            Some(summary) => summary,
+            None if output.status.success() => (self.echo_count, self.echo_count),
+            None => (self.echo_count, 0),
+        };
+        let rtt = parse_ping_rtt(&stdout);
+
+        Ok(IcmpStatistics {
+            addr,
+            transmitted,
+            received,
+            rtt_min: rtt.map(|rtt| rtt.0),
+            rtt_avg: rtt.map(|rtt| rtt.1),
+            rtt_max: rtt.map(|rtt| rtt.2),
+            rtt_mdev: rtt.and_then(|rtt| rtt.3),
+        })
+    }
+
+    /// Get the TTL (IPv4) or hop limit (IPv6), if set.
+    pub fn get_ttl(&self) -> Option<u8> {
+        self.ttl
+    }
+
+    /// Get the payload size in bytes, if set.
+    pub fn get_payload_size(&self) -> Option<u16> {
+        self.payload_size
+    }
+
+    /// Get the DSCP value, if set.
+    pub fn get_dscp(&self) -> Option<u8> {
+        self.dscp
+    }
+
+    /// Build the arguments of the ping command used to check given address.
+    ///
+    /// # Errors
+    /// Returns an error, if a TTL or DSCP value is set, but the flags of ping are unknown.
+    fn ping_args(&self, addr: &IpAddr, flags: Option<PingFlags>) -> Result<Vec<String>, CheckTargetError> {
+        let mut args = vec![String::from("-c"), self.echo_count.to_string()];
+        if let Some(flags) = &flags {
+            let timeout = match flags.timeout_millis {
+                true => (self.echo_timeout.as_nanos().div_ceil(1_000_000)).max(1).to_string(),
+                false => self.echo_timeout.as_secs_f64().to_string(),
+            };
+            args.extend([String::from(flags.timeout), timeout]);
+        }
+
+        if addr.is_ipv6() {
+            args.push(String::from("-6"));
+        }
+        if let Some(echo_interval) = self.echo_interval {
+            args.extend([String::from("-i"), echo_interval.as_secs_f64().to_string()]);
+        }
+        if let Some(ttl) = self.ttl {
+            let flags = flags
+                .as_ref()
+                .ok_or(CheckTargetError::from("TTL not supported by ping on this platform"))?;
+            args.extend([String::from(flags.ttl), ttl.to_string()]);
+        }
+        if let Some(payload_size) = self.payload_size {
+            args.extend([String::from("-s"), payload_size.to_string()]);
+        }
+        if let Some(dscp) = self.dscp {
+            let flags = flags
+                .as_ref()
+                .ok_or(CheckTargetError::from("DSCP not supported by ping on this platform"))?;
+            // Note: ping expects the whole TOS/traffic class byte. DSCP occupies the upper six bits.
+            args.extend([String::from(flags.tos), (dscp << 2).to_string()]);
+        }
+
+        args.push(addr.to_string());
+        Ok(args)
+    }
+
+    /// Evaluate the statistics of an address according to the configured thresholds.
+    fn is_success(&self, statistics: &IcmpStatistics) -> bool {
+        let received = f64::from(statistics.received);
+        let transmitted = f64::from(statistics.transmitted);
+        let exceeds = |value: Option<Duration>, max: Option<Duration>| match (value, max) {
+            (Some(value), Some(max)) => value > max,
+            _ => false,
+        };
+
+        received > 0.0
+            && received >= self.success_ratio * transmitted
+            && !exceeds(statistics.rtt_avg, self.max_rtt)
+            && !exceeds(statistics.rtt_mdev, self.max_jitter)
+    }
+
+    /// Evaluate the [Status] of an address according to the configured thresholds.
+    fn evaluate(&self, statistics: &IcmpStatistics) -> Status {
+        if !self.is_success(statistics) {
+            return Status::NotAvailable;
+        }
+
+        if let (Some(rtt), Some(threshold)) = (statistics.rtt_avg, self.degraded_rtt) {
+            if rtt > threshold {
+                return Status::Degraded(format!("average round trip time {:?} exceeds {:?}", rtt, threshold));
+            }
+        }
+        if let (Some(jitter), Some(threshold)) = (statistics.rtt_mdev, self.degraded_jitter) {
+            if jitter > threshold {
+                return Status::Degraded(format!("jitter {:?} exceeds {:?}", jitter, threshold));
+            }
+        }
+        if let Some(threshold) = self.degraded_loss_ratio {
+            let loss_ratio = statistics.loss_ratio();
+            if loss_ratio > threshold {
+                return Status::Degraded(format!(
+                    "packet loss {:.1}% exceeds {:.1}%",
+                    loss_ratio * 100.0,
+                    threshold * 100.0
+                ));
+            }
+        }
+        Status::Available
+    }
 }
 
 impl Target for IcmpTarget {
@@ -148,36 +507,131 @@ impl Target for IcmpTarget {
     fn check_availability(&self) -> Result<Status, CheckTargetError> {
         // Note: Spawn Ping to check if an ICMP target is available.
         // Using ping seems to be the easiest way to send ICMP packets without root privileges
-        let available_via_ping = |addr: IpAddr| {
-            if addr.is_ipv6() {
-                Command::new("ping")
-                    .stdout(Stdio::null())
-                    .arg("-c 1")
-                    .arg("-6")
-                    .arg(addr.to_string())
-                    .status()
-                    .unwrap()
-                    .success()
-            } else {
-                Command::new("ping")
-                    .stdout(Stdio::null())
-                    .arg("-c 1")
-                    .arg(addr.to_string())
-                    .status()
-                    .unwrap()
-                    .success()
+        // An available address takes precedence over degraded addresses.
+        let addrs = self.resolve_policy.resolve(&self.fqhn)?;
+        let mut degraded = None;
+        for addr in addrs.into_iter() {
+            match self.evaluate(&self.ping(addr)?) {
+                Status::Available => return Ok(Status::Available),
+                Status::Degraded(reason) => {
+                    degraded.get_or_insert(Status::Degraded(format!("{}: {}", addr, reason)));
+                }
+                _ => (),
             }
-        };
+        }
+        Ok(degraded.unwrap_or(Status::NotAvailable))
+    }
+}
 
-        let addrs = self.resolve_policy.resolve(&self.fqhn)?;
-        if addrs.into_iter().any(available_via_ping) {
-            Ok(Status::Available)
+/// Platform specific flags of the ping command.
+#[derive(Debug, PartialEq)]
+struct PingFlags {
+    /// Flag setting the time to wait for each echo reply.
+    timeout: &'static str,
+    /// True, if the timeout is given in milliseconds instead of seconds.
+    timeout_millis: bool,
+    /// Flag setting the TTL or hop limit.
+    ttl: &'static str,
+    /// Flag setting the TOS or traffic class byte.
+    tos: &'static str,
+}
+
+impl PingFlags {
+    /// Flags of ping from iputils, used by Linux.
+    fn linux() -> Self {
+        PingFlags {
+            timeout: "-W",
+            timeout_millis: false,
+            ttl: "-t",
+            tos: "-Q",
+        }
+    }
+
+    /// Flags of ping on macOS and FreeBSD.
+    fn bsd() -> Self {
+        PingFlags {
+            timeout: "-W",
+            timeout_millis: true,
+            ttl: "-m",
+            tos: "-z",
+        }
+    }
+
+    /// Flags of ping on the current platform, if known.
+    fn current() -> Option<Self> {
+        if cfg!(any(target_os = "linux", target_os = "android")) {
+            Some(PingFlags::linux())
+        } else if cfg!(any(target_os = "macos", target_os = "freebsd")) {
+            Some(PingFlags::bsd())
         } else {
-            Ok(Status::NotAvailable)
+            None
         }
     }
 }
 
+/// Statistics of the echo requests an [IcmpTarget] sent to a single address.
+#[derive(PartialEq, Debug, Clone)]
+pub struct IcmpStatistics {
+    /// Address the echo requests were sent to.
+    pub addr: IpAddr,
+    /// Number of echo requests sent.
+    pub transmitted: u32,
+    /// Number of echo replies received.
+    pub received: u32,
+    /// Minimum round trip time, if any reply was received.
+    pub rtt_min: Option<Duration>,
+    /// Average round trip time, if any reply was received.
+    pub rtt_avg: Option<Duration>,
+    /// Maximum round trip time, if any reply was received.
+    pub rtt_max: Option<Duration>,
+    /// Mean deviation of the round trip time (jitter), if reported by ping.
+    pub rtt_mdev: Option<Duration>,
+}
+
+impl IcmpStatistics {
+    /// Get the ratio of lost echo requests within [0.0, 1.0].
+    pub fn loss_ratio(&self) -> f64 {
+        if self.transmitted == 0 {
+            1.0
+        } else {
+            1.0 - (f64::from(self.received.min(self.transmitted)) / f64::from(self.transmitted))
+        }
+    }
+}
+
+/// Parse the summary line of the ping command, e.g.
+/// "5 packets transmitted, 4 received, 20% packet loss, time 4005ms".
+///
+/// # Returns
+/// The number of transmitted and received echo packets, if a summary was found.
+fn parse_ping_summary(output: &str) -> Option<(u32, u32)> {
+    let line = output.lines().find(|line| line.contains("packets transmitted"))?;
+    let mut fields = line.split(',').map(|field| field.split_whitespace().next());
+    let transmitted = fields.next()??.parse().ok()?;
+    let received = fields.next()??.parse().ok()?;
+    Some((transmitted, received))
+}
+
+/// Parse the round trip time line of the ping command, e.g.
+/// "rtt min/avg/max/mdev = 0.028/0.035/0.041/0.005 ms" or "round-trip min/avg/max = 0.1/0.2/0.3 ms".
+///
+/// # Returns
+/// The minimum, average, maximum and optionally the mean deviation of the round trip time.
+fn parse_ping_rtt(output: &str) -> Option<(Duration, Duration, Duration, Option<Duration>)> {
+    let line = output.lines().find(|line| line.contains("min/avg/max"))?;
+    let values = line.split('=').nth(1)?.split_whitespace().next()?;
+    let values: Vec<Duration> = values
+        .split('/')
+        .map(|value| value.parse::<f64>().ok().map(|ms| Duration::from_secs_f64(ms / 1000.0)))
+        .collect::<Option<_>>()?;
+
+    match values.as_slice() {
+        [min, avg, max] => Some((*min, *avg, *max, None)),
+        [min, avg, max, mdev] => Some((*min, *avg, *max, Some(*mdev))),
+        _ => None,
+    }
+}
+
 impl From<IpAddr> for IcmpTarget {
     fn from(addr: IpAddr) -> Self {
         IcmpTarget::new(addr.to_string(), ResolvePolicy::Agnostic)
@@ -229,6 +683,8 @@ pub struct TcpTarget {
     connect_timeout: Duration,
     /// [ResolvePolicy] to apply during resolution of fqhn to IP addresses.
     resolve_policy: ResolvePolicy,
+    /// Optional connect latency, above which a target is considered degraded.
+    degraded_latency: Option<Duration>,
 }
 
 impl TcpTarget {
@@ -251,6 +707,7 @@ impl TcpTarget {
             port,
             connect_timeout,
             resolve_policy,
+            degraded_latency: None,
         }
     }
 
@@ -267,6 +724,13 @@ impl TcpTarget {
         self
     }
 
+    /// Set the connect latency, above which an established connection is reported as
+    /// [Status::Degraded].
+    pub fn set_degraded_latency(mut self, degraded_latency: Duration) -> Self {
+        self.degraded_latency = Some(degraded_latency);
+        self
+    }
+
     /// Get a reference to the [Fqhn].
     pub fn get_fqhn(&self) -> &Fqhn {
         &self.fqhn
@@ -282,6 +746,11 @@ impl TcpTarget {
         &self.connect_timeout
     }
 
+    /// Get the connect latency, above which a connection is degraded, if set.
+    pub fn get_degraded_latency(&self) -> Option<&Duration> {
+        self.degraded_latency.as_ref()
+    }
+
     /// Get a reference to the [ResolvePolicy] in use.
     pub fn get_resolve_policy(&self) -> &ResolvePolicy {
         &self.resolve_policy
@@ -301,17 +770,27 @@ impl Target for TcpTarget {
         // Resolve and construct address/port pairs
         // Try for each address/port pair to establish a connection.
         // Occurring errors are treated as a sign of target is not available.
+        // Slow connections are treated as degraded, unless another address connects fast enough.
         let addrs = self.resolve_policy.resolve(&self.fqhn)?;
-        let available = addrs
-            .into_iter()
-            .map(|addr| SocketAddr::from((addr, self.port)))
-            .any(|addr| TcpStream::connect_timeout(&addr, self.connect_timeout).is_ok());
+        let mut degraded = None;
+        for addr in addrs.into_iter().map(|addr| SocketAddr::from((addr, self.port))) {
+            let start = Instant::now();
+            if TcpStream::connect_timeout(&addr, self.connect_timeout).is_err() {
+                continue;
+            }
 
-        if available {
-            Ok(Status::Available)
-        } else {
-            Ok(Status::NotAvailable)
+            let latency = start.elapsed();
+            match self.degraded_latency {
+                Some(threshold) if latency > threshold => {
+                    degraded.get_or_insert(Status::Degraded(format!(
+                        "{}: connect latency {:?} exceeds {:?}",
+                        addr, latency, threshold
+                    )));
+                }
+                _ => return Ok(Status::Available),
+            }
         }
+        Ok(degraded.unwrap_or(Status::NotAvailable))
     }
 }
 
@@ -402,6 +881,109 @@ impl FromStr for TcpTarget {
     }
 }
 
+/// Target combining multiple child [Target]s into a single [Target].
+///
+/// # Notes
+/// The [Status] of a CompositeTarget is derived from the status of its children:
+/// * [Status::Available], if all children are available.
+/// * [Status::NotAvailable], if no child is available or degraded.
+/// * [Status::Degraded] otherwise, the reason names all failing and degraded children.
+///
+/// Children returning an error or [Status::Unknown] are treated as not available.
+///
+/// # Example
+/// ```
+/// # use std::str::FromStr;
+/// # use reachable::{CompositeTarget, IcmpTarget, Target, TcpTarget};
+///
+/// let cluster = CompositeTarget::new(String::from("cluster"))
+///     .add_target(IcmpTarget::from_str("10.0.0.1").unwrap())
+///     .add_target(TcpTarget::from_str("10.0.0.2:443").unwrap());
+/// assert_eq!(cluster.get_id(), "cluster");
+/// ```
+pub struct CompositeTarget {
+    /// Identifier of this target.
+    id: String,
+    /// All child targets.
+    targets: Vec<Box<dyn Target + Send>>,
+}
+
+impl CompositeTarget {
+    /// Construct an empty [CompositeTarget].
+    ///
+    /// # Arguments
+    /// * id: identifier of the constructed target.
+    ///
+    /// # Returns
+    /// Instance of [CompositeTarget].
+    pub fn new(id: String) -> Self {
+        CompositeTarget {
+            id,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Add a child [Target].
+    pub fn add_target<T: Target + Send + 'static>(mut self, target: T) -> Self {
+        self.targets.push(Box::new(target));
+        self
+    }
+
+    /// Get a reference to all child targets.
+    pub fn get_targets(&self) -> &[Box<dyn Target + Send>] {
+        &self.targets
+    }
+}
+
+impl Target for CompositeTarget {
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn check_availability(&self) -> Result<Status, CheckTargetError> {
+        if self.targets.is_empty() {
+            return Err(CheckTargetError::from("CompositeTarget contains no targets"));
+        }
+
+        let mut failed = Vec::new();
+        let mut degraded = Vec::new();
+        for target in self.targets.iter() {
+            match target.check_availability() {
+                Ok(Status::Available) => (),
+                Ok(Status::Degraded(reason)) => degraded.push(format!("{}: {}", target.get_id(), reason)),
+                Ok(_) | Err(_) => failed.push(target.get_id()),
+            }
+        }
+
+        let total = self.targets.len();
+        if failed.len() == total {
+            return Ok(Status::NotAvailable);
+        }
+        if failed.is_empty() && degraded.is_empty() {
+            return Ok(Status::Available);
+        }
+
+        let mut reasons = Vec::new();
+        if !failed.is_empty() {
+            reasons.push(format!(
+                "{} of {} targets not available ({})",
+                failed.len(),
+                total,
+                failed.join(", ")
+            ));
+        }
+        if !degraded.is_empty() {
+            reasons.push(format!(
+                "{} of {} targets degraded ({})",
+                degraded.len(),
+                total,
+                degraded.join(", ")
+            ));
+        }
+        Ok(Status::Degraded(reasons.join("; ")))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::TcpListener;
@@ -453,6 +1035,194 @@ mod tests {
         assert_eq!(IcmpTarget::from(Ipv4Addr::LOCALHOST).get_id(), "127.0.0.1");
     }
 
+    #[test]
+    fn icmp_target_setters() {
+        // Expectency: The IcmpTarget setters store the echo configuration, enforcing valid ranges.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST);
+        assert_eq!(target.get_echo_count(), DEFAULT_ICMP_ECHO_COUNT);
+        assert_eq!(target.get_echo_timeout(), &DEFAULT_ICMP_ECHO_TIMEOUT);
+        assert_eq!(target.get_success_ratio(), 0.0);
+        assert_eq!(target.get_ttl(), None);
+        assert_eq!(target.get_payload_size(), None);
+        assert_eq!(target.get_dscp(), None);
+
+        let target = target
+            .set_echo_count(0)
+            .set_echo_timeout(Duration::from_millis(250))
+            .set_success_ratio(1.5)
+            .set_ttl(8)
+            .set_payload_size(1024)
+            .set_dscp(0xff);
+        assert_eq!(target.get_echo_count(), 1);
+        assert_eq!(target.get_echo_timeout(), &Duration::from_millis(250));
+        assert_eq!(target.get_success_ratio(), 1.0);
+        assert_eq!(target.get_ttl(), Some(8));
+        assert_eq!(target.get_payload_size(), Some(1024));
+        assert_eq!(target.get_dscp(), Some(0x3f));
+    }
+
+    #[test]
+    fn icmp_target_ping_args() {
+        // Expectency: The echo configuration must be translated into ping arguments.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST);
+        assert_eq!(
+            target
+                .ping_args(&IpAddr::V4(Ipv4Addr::LOCALHOST), Some(PingFlags::linux()))
+                .unwrap(),
+            vec!["-c", "1", "-W", "5", "127.0.0.1"]
+        );
+
+        let target = target
+            .set_echo_count(3)
+            .set_echo_timeout(Duration::from_millis(500))
+            .set_echo_interval(Duration::from_millis(200))
+            .set_ttl(16)
+            .set_payload_size(64)
+            .set_dscp(46);
+        assert_eq!(
+            target
+                .ping_args(&IpAddr::V6(Ipv6Addr::LOCALHOST), Some(PingFlags::linux()))
+                .unwrap(),
+            vec!["-c", "3", "-W", "0.5", "-6", "-i", "0.2", "-t", "16", "-s", "64", "-Q", "184", "::1"]
+        );
+    }
+
+    #[test]
+    fn icmp_target_ping_args_platforms() {
+        // Expectency: On macOS and FreeBSD, the timeout is given in milliseconds and TTL and TOS
+        //             use other flags. Without known flags, TTL and DSCP are rejected.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST)
+            .set_echo_timeout(Duration::from_micros(500))
+            .set_ttl(16)
+            .set_dscp(46);
+        let addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert_eq!(
+            target.ping_args(&addr, Some(PingFlags::bsd())).unwrap(),
+            vec!["-c", "1", "-W", "1", "-m", "16", "-z", "184", "127.0.0.1"]
+        );
+        assert_eq!(
+            format!("{}", target.ping_args(&addr, None).unwrap_err()),
+            "TTL not supported by ping on this platform"
+        );
+        assert_eq!(
+            IcmpTarget::from(Ipv4Addr::LOCALHOST).ping_args(&addr, None).unwrap(),
+            vec!["-c", "1", "127.0.0.1"]
+        );
+        if cfg!(target_os = "linux") {
+            assert_eq!(PingFlags::current(), Some(PingFlags::linux()));
+        }
+    }
+
+    fn statistics(transmitted: u32, received: u32, rtt: Option<(u64, u64)>) -> IcmpStatistics {
+        IcmpStatistics {
+            addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            transmitted,
+            received,
+            rtt_min: rtt.map(|(avg, _)| Duration::from_millis(avg)),
+            rtt_avg: rtt.map(|(avg, _)| Duration::from_millis(avg)),
+            rtt_max: rtt.map(|(avg, _)| Duration::from_millis(avg)),
+            rtt_mdev: rtt.map(|(_, mdev)| Duration::from_millis(mdev)),
+        }
+    }
+
+    #[test]
+    fn icmp_target_success_ratio() {
+        // Expectency: At least one reply is required, additionally the success ratio must be met.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST).set_echo_count(5);
+        assert!(!target.is_success(&statistics(5, 0, None)));
+        assert!(target.is_success(&statistics(5, 1, None)));
+
+        let target = target.set_success_ratio(0.6);
+        assert!(!target.is_success(&statistics(5, 2, None)));
+        assert!(target.is_success(&statistics(5, 3, None)));
+    }
+
+    #[test]
+    fn icmp_target_latency_thresholds() {
+        // Expectency: Exceeding the maximum average round trip time or jitter must be treated as
+        //             failure.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST)
+            .set_max_rtt(Duration::from_millis(100))
+            .set_max_jitter(Duration::from_millis(20));
+        assert!(target.is_success(&statistics(5, 5, Some((100, 20)))));
+        assert!(!target.is_success(&statistics(5, 5, Some((101, 20)))));
+        assert!(!target.is_success(&statistics(5, 5, Some((100, 21)))));
+    }
+
+    #[test]
+    fn icmp_statistics_loss_ratio() {
+        // Expectency: The loss ratio is derived from the number of transmitted and received echos.
+        assert_eq!(statistics(4, 4, None).loss_ratio(), 0.0);
+        assert_eq!(statistics(4, 3, None).loss_ratio(), 0.25);
+        assert_eq!(statistics(4, 0, None).loss_ratio(), 1.0);
+        assert_eq!(statistics(0, 0, None).loss_ratio(), 1.0);
+    }
+
+    #[test]
+    fn icmp_target_degraded_thresholds() {
+        // Expectency: Available addresses exceeding a degradation threshold must be reported as
+        //             degraded, addresses failing the availability thresholds as not available.
+        let target = IcmpTarget::from(Ipv4Addr::LOCALHOST)
+            .set_max_rtt(Duration::from_millis(200))
+            .set_degraded_rtt(Duration::from_millis(100))
+            .set_degraded_jitter(Duration::from_millis(20))
+            .set_degraded_loss_ratio(0.2);
+        assert_eq!(target.evaluate(&statistics(5, 5, Some((100, 20)))), Status::Available);
+        assert_eq!(
+            target.evaluate(&statistics(5, 5, Some((150, 20)))),
+            Status::Degraded(String::from("average round trip time 150ms exceeds 100ms"))
+        );
+        assert_eq!(
+            target.evaluate(&statistics(5, 5, Some((100, 30)))),
+            Status::Degraded(String::from("jitter 30ms exceeds 20ms"))
+        );
+        assert_eq!(
+            target.evaluate(&statistics(5, 3, Some((100, 20)))),
+            Status::Degraded(String::from("packet loss 40.0% exceeds 20.0%"))
+        );
+        assert_eq!(
+            target.evaluate(&statistics(5, 5, Some((250, 20)))),
+            Status::NotAvailable
+        );
+    }
+
+    #[test]
+    fn icmp_target_parse_ping_rtt() {
+        // Expectency: The round trip times must be extracted from the output of iputils and
+        //             busybox ping.
+        assert_eq!(
+            parse_ping_rtt("rtt min/avg/max/mdev = 1.000/2.500/4.000/0.500 ms"),
+            Some((
+                Duration::from_millis(1),
+                Duration::from_micros(2500),
+                Duration::from_millis(4),
+                Some(Duration::from_micros(500))
+            ))
+        );
+        assert_eq!(
+            parse_ping_rtt("round-trip min/avg/max = 1.000/2.000/3.000 ms"),
+            Some((
+                Duration::from_millis(1),
+                Duration::from_millis(2),
+                Duration::from_millis(3),
+                None
+            ))
+        );
+        assert_eq!(parse_ping_rtt("5 packets transmitted, 0 received"), None);
+    }
+
+    #[test]
+    fn icmp_target_parse_ping_summary() {
+        // Expectency: The number of transmitted and received packets must be extracted from
+        //             the output of ping. Missing summaries lead to None.
+        let output = "PING 127.0.0.1 (127.0.0.1) 56(84) bytes of data.\n\
+                      64 bytes from 127.0.0.1: icmp_seq=1 ttl=64 time=0.041 ms\n\n\
+                      --- 127.0.0.1 ping statistics ---\n\
+                      5 packets transmitted, 4 received, 20% packet loss, time 4005ms\n";
+        assert_eq!(parse_ping_summary(output), Some((5, 4)));
+        assert_eq!(parse_ping_summary("ping: unknown host"), None);
+    }
+
     #[test]
     fn icmp_target_check_availability() {
         // Expectency: LOCALHOST must always be available without any errors
@@ -641,6 +1411,22 @@ mod tests {
         srv.join().unwrap();
     }
 
+    #[test]
+    fn tcp_target_check_degraded() {
+        // Expectency: check_availability must return Status::Degraded if a connection was
+        //             established, but took longer than the degraded latency.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let srv = spawn(move || listener.accept().unwrap());
+
+        let target = TcpTarget::from((Ipv4Addr::LOCALHOST, port)).set_degraded_latency(Duration::ZERO);
+        match target.check_availability().unwrap() {
+            Status::Degraded(reason) => assert!(reason.starts_with(&format!("127.0.0.1:{}: connect latency", port))),
+            status => panic!("Unexpected status {}", status),
+        }
+        srv.join().unwrap();
+    }
+
     #[test]
     fn tcp_target_check_unavailability() {
         // Expectency: check_availability must return Status::NotAvailable if on a closed port.
@@ -673,6 +1459,60 @@ mod tests {
         );
     }
 
+    // Status tests
+    #[test]
+    fn status_display() {
+        // Expectency: Each status must be displayed in a human readable way.
+        assert_eq!(format!("{}", Status::Unknown), "unknown");
+        assert_eq!(format!("{}", Status::Available), "available");
+        assert_eq!(format!("{}", Status::Degraded(String::from("slow"))), "degraded (slow)");
+        assert_eq!(format!("{}", Status::NotAvailable), "not available");
+    }
+
+    // CompositeTarget tests
+    fn mock_target(id: &'static str, result: fn() -> Result<Status, CheckTargetError>) -> MockTarget {
+        let mut mock = MockTarget::new();
+        mock.expect_get_id().returning(move || String::from(id));
+        mock.expect_check_availability().times(1).returning(result);
+        mock
+    }
+
+    #[test]
+    fn composite_target_check_availability() {
+        // Expectency: A CompositeTarget is available if all children are available, not
+        //             available if all children fail and degraded otherwise.
+        let target = CompositeTarget::new(String::from("all"))
+            .add_target(mock_target("a", || Ok(Status::Available)))
+            .add_target(mock_target("b", || Ok(Status::Available)));
+        assert_eq!(target.check_availability().unwrap(), Status::Available);
+
+        let target = CompositeTarget::new(String::from("none"))
+            .add_target(mock_target("a", || Ok(Status::NotAvailable)))
+            .add_target(mock_target("b", || Err(CheckTargetError::from("Error"))));
+        assert_eq!(target.check_availability().unwrap(), Status::NotAvailable);
+
+        let target = CompositeTarget::new(String::from("some"))
+            .add_target(mock_target("a", || Ok(Status::Available)))
+            .add_target(mock_target("b", || Ok(Status::NotAvailable)))
+            .add_target(mock_target("c", || Ok(Status::Degraded(String::from("slow")))));
+        assert_eq!(
+            target.check_availability().unwrap(),
+            Status::Degraded(String::from(
+                "1 of 3 targets not available (b); 1 of 3 targets degraded (c: slow)"
+            ))
+        );
+    }
+
+    #[test]
+    fn composite_target_empty() {
+        // Expectency: A CompositeTarget without children must return an error.
+        let target = CompositeTarget::new(String::from("empty"));
+        assert_eq!(
+            format!("{}", target.check_availability().unwrap_err()),
+            "CompositeTarget contains no targets"
+        );
+    }
+
     #[test]
     fn tcp_target_check_availability_all_addresses_filtered_error_v6() {
         // Expectency: check_availability must return an error if all resolved
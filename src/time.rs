@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Private module converting points in time from and to UTC calendar dates.
+
+// Imports
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Calendar representation of a point in time in UTC.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CivilTime {
+    pub(crate) year: u64,
+    pub(crate) month: u64,
+    pub(crate) day: u64,
+    pub(crate) hour: u64,
+    pub(crate) minute: u64,
+    pub(crate) second: u64,
+    /// Day of week, 0 is Sunday.
+    pub(crate) weekday: u64,
+}
+
+impl From<u64> for CivilTime {
+    /// Convert given seconds since the unix epoch.
+    fn from(time: u64) -> Self {
+        // Note: Conversion from days to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+        let days = time / 86400;
+        let era = (days + 719468) / 146097;
+        let day_of_era = (days + 719468) % 146097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+
+        CivilTime {
+            year: year_of_era + era * 400 + if month <= 2 { 1 } else { 0 },
+            month,
+            day: day_of_year - (153 * month_index + 2) / 5 + 1,
+            hour: time % 86400 / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+            // Note: The unix epoch was a Thursday
+            weekday: (days + 4) % 7,
+        }
+    }
+}
+
+impl From<SystemTime> for CivilTime {
+    fn from(time: SystemTime) -> Self {
+        CivilTime::from(unix_time(time))
+    }
+}
+
+/// Latest year of a calendar date accepted when parsing points in time.
+pub(crate) const MAX_YEAR: u64 = 9999;
+
+/// Get the number of days of given month (1-12) in given year.
+pub(crate) fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the number of days since the unix epoch of given date.
+pub(crate) fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Get the seconds since the unix epoch. Points in time before the epoch are treated as epoch.
+pub(crate) fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Format given seconds since the unix epoch as UTC date and time, e.g. "2021-10-03 12:00:00".
+pub(crate) fn format_time(time: u64) -> String {
+    let time = CivilTime::from(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        time.year, time.month, time.day, time.hour, time.minute, time.second
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn civil_time_from_unix_time() {
+        // Expectency: Seconds since the unix epoch are converted into the UTC calendar date,
+        //             including leap days and the day of week.
+        let expected = CivilTime {
+            year: 2000,
+            month: 2,
+            day: 29,
+            hour: 1,
+            minute: 2,
+            second: 3,
+            weekday: 2,
+        };
+        assert_eq!(CivilTime::from(951782400 + 3723), expected);
+        assert_eq!(
+            CivilTime::from(UNIX_EPOCH + Duration::from_secs(951782400 + 3723)),
+            expected
+        );
+        assert_eq!(days_from_civil(2000, 2, 29), 951782400 / 86400);
+    }
+
+    #[test]
+    fn days_in_month_leap_years() {
+        // Expectency: February has 29 days in years divisible by 4, except centuries not
+        //             divisible by 400.
+        assert_eq!(days_in_month(2021, 1), 31);
+        assert_eq!(days_in_month(2021, 4), 30);
+        assert_eq!(days_in_month(2021, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn unix_time_before_epoch() {
+        // Expectency: Points in time before the unix epoch are treated as epoch.
+        assert_eq!(unix_time(UNIX_EPOCH - Duration::from_secs(1)), 0);
+    }
+
+    #[test]
+    fn format_time_utc() {
+        // Expectency: Seconds since the unix epoch are formatted as UTC date and time.
+        assert_eq!(format_time(0), "1970-01-01 00:00:00");
+        assert_eq!(format_time(951782400), "2000-02-29 00:00:00");
+        assert_eq!(format_time(1633262400 + 3661), "2021-10-03 13:01:01");
+    }
+}
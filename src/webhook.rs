@@ -0,0 +1,518 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Author: Simon Brummer (simon.brummer@posteo.de)
+
+//! Module contains a webhook notifier, posting the status transitions of the targets of an
+//! [AsyncTargetExecutor] as JSON to an HTTP endpoint, e.g. a chat or incident webhook.
+//!
+//! Transitions are delivered by a background thread:
+//! * Transitions occurring within the batch window are sent together in a single request.
+//! * Consecutive requests are at least the minimum interval apart. Transitions occurring
+//!   meanwhile are batched into the next request.
+//! * Failed deliveries are retried with exponential backoff.
+//!
+//! Transitions during a maintenance window of a target, caused by an unreachable parent or of a
+//! flapping target are not delivered.
+//!
+//! # Templates
+//! The payload is built from two templates. Each transition is rendered with the transition
+//! template, supporting the placeholders `{{id}}`, `{{status}}`, `{{old_status}}`, `{{error}}`
+//! and `{{timestamp}}` (seconds since the unix epoch). The payload template supports the
+//! placeholders `{{transitions}}`, containing all rendered transitions separated by commas, and
+//! `{{summary}}`, containing a human readable line per transition. All values are escaped for
+//! use within JSON strings. Placeholders contained in values, e.g. in an error message, are not
+//! replaced.
+//!
+//! # Example
+//! ```no_run
+//! # use std::str::FromStr;
+//! # use reachable::*;
+//! # use reachable::webhook::WebhookNotifier;
+//! let notifier = WebhookNotifier::from_str("http://127.0.0.1:8080/hook")
+//!     .unwrap()
+//!     .set_payload_template(String::from("{\"text\":\"{{summary}}\"}"));
+//!
+//! let mut exec = AsyncTargetExecutor::new();
+//! exec.add_observer(notifier);
+//! ```
+//!
+//! # Notes
+//! Requires crate to be configured with feature "webhook". Only plain HTTP is supported.
+
+use super::async_target::{CheckObserver, CheckReport};
+use super::http::{self, HttpUrl};
+use super::WebhookError;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+// Documentation imports
+#[cfg(doc)]
+use super::{AsyncTargetExecutor, Status};
+
+/// Default template of a single transition.
+const TRANSITION_TEMPLATE: &str = concat!(
+    "{\"target\":\"{{id}}\",\"status\":\"{{status}}\",\"old_status\":\"{{old_status}}\",",
+    "\"error\":\"{{error}}\",\"timestamp\":{{timestamp}}}"
+);
+
+/// Default template of the payload.
+const PAYLOAD_TEMPLATE: &str = "{\"transitions\":[{{transitions}}]}";
+
+/// Type containing a boxed trait object implementing [FnMut], called with each failed delivery.
+pub type BoxedErrorHandler = Box<dyn FnMut(WebhookError) + Send>;
+
+/// Status transition of a single target.
+#[derive(Debug)]
+struct Transition {
+    id: String,
+    status: String,
+    old_status: String,
+    error: String,
+    timestamp: u64,
+}
+
+/// Settings of the delivery thread.
+struct Settings {
+    url: HttpUrl,
+    transition_template: String,
+    payload_template: String,
+    batch_window: Duration,
+    min_interval: Duration,
+    retries: u32,
+    backoff: Duration,
+    timeout: Duration,
+    error_handler: Option<BoxedErrorHandler>,
+}
+
+/// Mutable state of a [WebhookNotifier].
+#[derive(Default)]
+struct State {
+    /// Channel to the delivery thread. The thread is started with the first transition.
+    sender: Option<Sender<Transition>>,
+    /// Ids of all targets checked at least once.
+    seen: HashSet<String>,
+}
+
+/// Notifier posting status transitions to a webhook.
+///
+/// Register the notifier with [AsyncTargetExecutor::add_observer]. A transition is a change of
+/// the state of a target, changes of the reason of [Status::Degraded] are no transitions.
+pub struct WebhookNotifier {
+    settings: Mutex<Option<Settings>>,
+    notify_initial: bool,
+    state: Mutex<State>,
+}
+
+impl WebhookNotifier {
+    /// Set the template of a single transition. See [module documentation](self) for details.
+    pub fn set_transition_template(self, template: String) -> Self {
+        self.update(|settings| settings.transition_template = template)
+    }
+
+    /// Set the template of the payload. See [module documentation](self) for details.
+    pub fn set_payload_template(self, template: String) -> Self {
+        self.update(|settings| settings.payload_template = template)
+    }
+
+    /// Set the time to wait for further transitions, before a batch is sent. Defaults to 1s.
+    pub fn set_batch_window(self, batch_window: Duration) -> Self {
+        self.update(|settings| settings.batch_window = batch_window)
+    }
+
+    /// Set the minimum time between two requests. Defaults to 0s.
+    pub fn set_min_interval(self, min_interval: Duration) -> Self {
+        self.update(|settings| settings.min_interval = min_interval)
+    }
+
+    /// Set the number of retries of a failed delivery. Defaults to 3.
+    pub fn set_retries(self, retries: u32) -> Self {
+        self.update(|settings| settings.retries = retries)
+    }
+
+    /// Set the delay before the first retry. The delay doubles with each retry. Defaults to 1s.
+    pub fn set_backoff(self, backoff: Duration) -> Self {
+        self.update(|settings| settings.backoff = backoff)
+    }
+
+    /// Set the timeout of a single request. Defaults to 5s.
+    pub fn set_timeout(self, timeout: Duration) -> Self {
+        self.update(|settings| settings.timeout = timeout)
+    }
+
+    /// Set a handler called with the error of each batch that could not be delivered.
+    pub fn set_error_handler(self, error_handler: BoxedErrorHandler) -> Self {
+        self.update(|settings| settings.error_handler = Some(error_handler))
+    }
+
+    /// Set if the result of the first check of a target is notified as transition from
+    /// [Status::Unknown]. Defaults to false.
+    pub fn set_notify_initial(mut self, notify_initial: bool) -> Self {
+        self.notify_initial = notify_initial;
+        self
+    }
+
+    /// Apply given function onto the settings.
+    fn update<F: FnOnce(&mut Settings)>(self, function: F) -> Self {
+        if let Some(settings) = self.settings.lock().unwrap().as_mut() {
+            function(settings);
+        }
+        self
+    }
+}
+
+impl FromStr for WebhookNotifier {
+    type Err = WebhookError;
+
+    /// Construct a [WebhookNotifier] posting to given URL, e.g. "http://127.0.0.1:8080/hook".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let url = HttpUrl::parse(s).ok_or(WebhookError::from("Invalid URL, expected http://host[:port][/path]"))?;
+        let settings = Settings {
+            url,
+            transition_template: String::from(TRANSITION_TEMPLATE),
+            payload_template: String::from(PAYLOAD_TEMPLATE),
+            batch_window: Duration::from_secs(1),
+            min_interval: Duration::ZERO,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            timeout: Duration::from_secs(5),
+            error_handler: None,
+        };
+
+        Ok(WebhookNotifier {
+            settings: Mutex::new(Some(settings)),
+            notify_initial: false,
+            state: Mutex::default(),
+        })
+    }
+}
+
+impl CheckObserver for WebhookNotifier {
+    fn on_check(&self, report: &CheckReport) {
+        let mut state = self.state.lock().unwrap();
+        let initial = state.seen.insert(String::from(report.id));
+        let expected = report.maintenance || report.unreachable_parent.is_some() || report.flapping;
+        if report.status.same_state(report.old_status) || (initial && !self.notify_initial) || expected {
+            return;
+        }
+
+        let transition = Transition {
+            id: String::from(report.id),
+            status: report.status.to_string(),
+            old_status: report.old_status.to_string(),
+            error: report.error.map(|error| error.to_string()).unwrap_or_default(),
+            timestamp: report
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+
+        // Start delivery thread on first transition
+        if state.sender.is_none() {
+            if let Some(settings) = self.settings.lock().unwrap().take() {
+                let (sender, receiver) = mpsc::channel();
+                thread::spawn(move || deliver_batches(settings, receiver));
+                state.sender = Some(sender);
+            }
+        }
+        if let Some(sender) = &state.sender {
+            let _ = sender.send(transition);
+        }
+    }
+
+    fn on_remove(&self, id: &str) {
+        self.state.lock().unwrap().seen.remove(id);
+    }
+}
+
+/// Collect transitions into batches and deliver them until the notifier was dropped.
+fn deliver_batches(mut settings: Settings, receiver: Receiver<Transition>) {
+    let mut last_delivery: Option<Instant> = None;
+    while let Ok(transition) = receiver.recv() {
+        // Collect transitions until the batch window elapsed and the minimum interval is kept.
+        let mut batch = vec![transition];
+        let mut deadline = Instant::now() + settings.batch_window;
+        if let Some(last_delivery) = last_delivery {
+            deadline = deadline.max(last_delivery + settings.min_interval);
+        }
+
+        let mut disconnected = false;
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            match receiver.recv_timeout(timeout) {
+                Ok(transition) => batch.push(transition),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+
+        let payload = render_payload(&settings, &batch);
+        if let Err(error) = deliver(&settings, &payload) {
+            if let Some(error_handler) = settings.error_handler.as_mut() {
+                error_handler(error);
+            }
+        }
+        last_delivery = Some(Instant::now());
+
+        if disconnected {
+            break;
+        }
+    }
+}
+
+/// Post given payload, retry with exponential backoff on failure.
+fn deliver(settings: &Settings, payload: &str) -> Result<(), WebhookError> {
+    let mut backoff = settings.backoff;
+    let mut attempts = 0;
+    loop {
+        let error = match http::post(&settings.url, "application/json", payload, settings.timeout) {
+            Ok(status) if (200..300).contains(&status) => return Ok(()),
+            Ok(status) => WebhookError::HttpStatus(status),
+            Err(error) => WebhookError::from(("Failed to deliver notification", error)),
+        };
+
+        attempts += 1;
+        if attempts > settings.retries {
+            return Err(error);
+        }
+        thread::sleep(backoff);
+        backoff *= 2;
+    }
+}
+
+/// Render the payload of given batch of transitions.
+fn render_payload(settings: &Settings, batch: &[Transition]) -> String {
+    let transitions: Vec<String> = batch
+        .iter()
+        .map(|transition| {
+            render_template(&settings.transition_template, |placeholder| match placeholder {
+                "id" => Some(escape_json(&transition.id)),
+                "status" => Some(escape_json(&transition.status)),
+                "old_status" => Some(escape_json(&transition.old_status)),
+                "error" => Some(escape_json(&transition.error)),
+                "timestamp" => Some(transition.timestamp.to_string()),
+                _ => None,
+            })
+        })
+        .collect();
+    let summary: Vec<String> = batch
+        .iter()
+        .map(|transition| {
+            let summary = format!(
+                "Target \"{}\" changed from \"{}\" to \"{}\"",
+                transition.id, transition.old_status, transition.status
+            );
+            match transition.error.as_str() {
+                "" => summary,
+                error => format!("{}, Error: \"{}\"", summary, error),
+            }
+        })
+        .collect();
+
+    render_template(&settings.payload_template, |placeholder| match placeholder {
+        "transitions" => Some(transitions.join(",")),
+        "summary" => Some(escape_json(&summary.join("\n"))),
+        _ => None,
+    })
+}
+
+/// Render given template in a single pass: Each placeholder `{{name}}` is replaced by the value
+/// of its name. Unknown placeholders are kept, inserted values are never rendered again.
+fn render_template<F: Fn(&str) -> Option<String>>(template: &str, value: F) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let replacement = rest
+            .find("}}")
+            .and_then(|end| value(&rest[2..end]).map(|value| (end, value)));
+        match replacement {
+            Some((end, value)) => {
+                rendered.push_str(&value);
+                rest = &rest[end + 2..];
+            }
+            None => {
+                rendered.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Escape given text for use within a JSON string.
+fn escape_json(text: &str) -> String {
+    let quoted = serde_json::Value::from(text).to_string();
+    String::from(&quoted[1..quoted.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::http::{BoxedRoutes, HttpServer, Request, Response};
+    use crate::{CheckTargetError, Status};
+
+    fn report<'a>(
+        id: &'a str,
+        status: &'a Status,
+        old_status: &'a Status,
+        error: Option<&'a CheckTargetError>,
+    ) -> CheckReport<'a> {
+        CheckReport::new(
+            id,
+            status,
+            old_status,
+            error,
+            Duration::from_millis(10),
+            UNIX_EPOCH + Duration::from_secs(42),
+        )
+    }
+
+    /// Start a local webhook stand-in. The first given number of requests fail with status 503.
+    fn stand_in(failures: usize) -> (HttpServer, Receiver<String>) {
+        let (send, recv) = mpsc::channel();
+        let send = Mutex::new(send);
+        let requests = AtomicUsize::new(0);
+        let routes: BoxedRoutes = Box::new(move |request: &Request| {
+            if requests.fetch_add(1, Ordering::SeqCst) < failures {
+                return Response::error(503);
+            }
+            send.lock().unwrap().send(request.body.clone()).unwrap();
+            Response::ok("text/plain", String::new())
+        });
+        (HttpServer::bind("127.0.0.1:0", routes).unwrap(), recv)
+    }
+
+    fn notifier(server: &HttpServer) -> WebhookNotifier {
+        WebhookNotifier::from_str(&format!("http://{}/hook", server.local_addr()))
+            .unwrap()
+            .set_batch_window(Duration::from_millis(200))
+            .set_backoff(Duration::from_millis(10))
+    }
+
+    #[test]
+    fn webhook_notifier_from_str_invalid() {
+        // Expectency: Only http URLs are accepted.
+        assert!(WebhookNotifier::from_str("https://localhost/hook").is_err());
+        assert!(WebhookNotifier::from_str("localhost").is_err());
+    }
+
+    #[test]
+    fn webhook_notifier_batch_transitions() {
+        // Expectency: Simultaneous transitions are sent in a single request. Initial checks and
+        //             checks without state change are not notified.
+        let (server, recv) = stand_in(0);
+        let notifier = notifier(&server);
+        let error = CheckTargetError::from("Error");
+
+        notifier.on_check(&report("a", &Status::Available, &Status::Unknown, None));
+        notifier.on_check(&report("b", &Status::Available, &Status::Unknown, None));
+        notifier.on_check(&report("a", &Status::Available, &Status::Available, None));
+        notifier.on_check(&report("a", &Status::NotAvailable, &Status::Available, None));
+        notifier.on_check(&report("b", &Status::Unknown, &Status::Available, Some(&error)));
+
+        let payload = recv.recv_timeout(Duration::from_secs(5)).unwrap();
+        let expected = concat!(
+            "{\"transitions\":[",
+            "{\"target\":\"a\",\"status\":\"not available\",\"old_status\":\"available\",\"error\":\"\",\"timestamp\":42},",
+            "{\"target\":\"b\",\"status\":\"unknown\",\"old_status\":\"available\",\"error\":\"Error\",\"timestamp\":42}",
+            "]}"
+        );
+        assert_eq!(payload, expected);
+        assert!(recv.recv_timeout(Duration::from_millis(400)).is_err());
+    }
+
+    #[test]
+    fn webhook_notifier_retry() {
+        // Expectency: A failed delivery is retried, a custom payload template is used.
+        let (server, recv) = stand_in(2);
+        let notifier = notifier(&server)
+            .set_payload_template(String::from("{\"text\":\"{{summary}}\"}"))
+            .set_notify_initial(true);
+
+        notifier.on_check(&report("a", &Status::Available, &Status::Unknown, None));
+        let payload = recv.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            payload,
+            "{\"text\":\"Target \\\"a\\\" changed from \\\"unknown\\\" to \\\"available\\\"\"}"
+        );
+    }
+
+    #[test]
+    fn webhook_notifier_give_up() {
+        // Expectency: After all retries were used up, the error handler is called.
+        let (server, _recv) = stand_in(usize::MAX);
+        let (send, recv) = mpsc::channel();
+        let send = Mutex::new(send);
+        let notifier = notifier(&server)
+            .set_retries(1)
+            .set_notify_initial(true)
+            .set_error_handler(Box::new(move |error| {
+                send.lock().unwrap().send(error.to_string()).unwrap();
+            }));
+
+        notifier.on_check(&report("a", &Status::Available, &Status::Unknown, None));
+        let error = recv.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(error, "Unexpected HTTP status 503");
+    }
+
+    #[test]
+    fn webhook_notifier_escape_json() {
+        // Expectency: Values are escaped for use within JSON strings.
+        assert_eq!(escape_json("a\"b\\c\nd\u{1}"), "a\\\"b\\\\c\\nd\\u0001");
+    }
+
+    #[test]
+    fn webhook_notifier_render_template() {
+        // Expectency: Placeholders are replaced in a single pass. Placeholders within inserted
+        //             values and unknown placeholders are kept as they are.
+        let value = |placeholder: &str| match placeholder {
+            "id" => Some(String::from("{{error}}")),
+            "error" => Some(String::from("Error")),
+            _ => None,
+        };
+        assert_eq!(
+            render_template("{\"id\":\"{{id}}\",\"error\":\"{{error}}\"}", value),
+            "{\"id\":\"{{error}}\",\"error\":\"Error\"}"
+        );
+        assert_eq!(render_template("{{{{id}}}}", value), "{{{{error}}}}");
+        assert_eq!(render_template("{{unknown}} {{id", value), "{{unknown}} {{id");
+    }
+
+    #[test]
+    fn webhook_notifier_render_payload_injection() {
+        // Expectency: Placeholders within target ids and errors are not expanded.
+        let settings = WebhookNotifier::from_str("http://localhost/hook")
+            .unwrap()
+            .settings
+            .into_inner()
+            .unwrap()
+            .unwrap();
+        let transition = Transition {
+            id: String::from("{{timestamp}}"),
+            status: String::from("unknown"),
+            old_status: String::from("available"),
+            error: String::from("{{status}}"),
+            timestamp: 42,
+        };
+        assert_eq!(
+            render_payload(&settings, &[transition]),
+            concat!(
+                "{\"transitions\":[{\"target\":\"{{timestamp}}\",\"status\":\"unknown\",",
+                "\"old_status\":\"available\",\"error\":\"{{status}}\",\"timestamp\":42}]}"
+            )
+        );
+    }
+}